@@ -1,8 +1,15 @@
 pub mod main_code;
 
-use crate::main_code::core::game::Game;
+use crate::main_code::core::{
+    game::Game,
+    game_parameters::{AbstractParameters, GameParameters},
+};
 use clap::Parser;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::{
+    path::PathBuf,
+    process,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -19,9 +26,17 @@ struct Args {
     #[arg(short = 'p', long, default_value_t = 0)]
     turn_pause: i8,
 
-    /// Seed for random number generation
+    /// Game seed, from which the seeds of the players and redeterminations are derived
     #[arg(short, long)]
     seed: Option<u64>,
+
+    /// JSON file with the game parameters
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Sample the game parameters at random from their possible values
+    #[arg(short, long, default_value_t = false)]
+    randomize_parameters: bool,
 }
 
 fn main() {
@@ -40,7 +55,19 @@ fn main() {
     println!("Turn pause: {}", args.turn_pause);
     println!("Seed: {}", seed);
 
-    let game = Game::new();
+    let mut game_parameters = AbstractParameters::new();
+    if let Some(config) = &args.config {
+        if let Err(e) = game_parameters.load_json_file(config) {
+            eprintln!("Error loading {}: {}", config.display(), e);
+            process::exit(1);
+        }
+        println!("Config: {}", config.display());
+    }
+    println!("Parameters: {}", game_parameters.to_json());
+
+    let mut game = Game::new_with_parameters(Box::new(game_parameters));
+    game.set_seed(seed);
+    game.set_randomize_parameters(args.randomize_parameters);
     game.main();
 
     // ... rest of your program
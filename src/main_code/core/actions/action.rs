@@ -1,4 +1,4 @@
-use crate::main_code::core::{game_state::GameState, glu::glu::GLU};
+use crate::main_code::core::{game_state::GameState, id_allocator::IdAllocator};
 use std::{collections::HashSet, hash::Hash};
 
 pub trait Action: dyn_clone::DynClone + downcast_rs::Downcast {
@@ -28,6 +28,7 @@ pub trait Action: dyn_clone::DynClone + downcast_rs::Downcast {
     }
 
     /// Returns a tooltip for the GUI representation of the action.
+    #[allow(clippy::borrowed_box)]
     fn get_tooltip(&self, _gs: &Box<dyn GameState>) -> String {
         String::from("")
     }
@@ -44,8 +45,12 @@ pub struct AbstractAction {
 }
 
 impl AbstractAction {
-    pub fn new() -> Self {
-        Self { id: GLU::next_id() }
+    /// Creates an action whose ID is taken from the action IDs of the game (see
+    /// GameState::action_ids)
+    pub fn new(ids: &mut IdAllocator) -> Self {
+        Self {
+            id: ids.next_id() as i32,
+        }
     }
 
     pub fn with_id(id: i32) -> Self {
@@ -0,0 +1,103 @@
+use crate::main_code::core::{
+    actions::action::{AbstractAction, Action},
+    components::counter::Counter,
+    game_state::GameState,
+    id_allocator::IdAllocator,
+};
+use std::hash::{Hash, Hasher};
+
+/// Adds an amount (negative to subtract) to a counter, within its bounds. The counter must be
+/// registered in the game state.
+#[derive(Clone)]
+pub struct ChangeCounter {
+    data: AbstractAction,
+    counter: usize,
+    amount: i64,
+}
+
+impl ChangeCounter {
+    pub fn new(counter: usize, amount: i64, ids: &mut IdAllocator) -> Self {
+        Self {
+            data: AbstractAction::new(ids),
+            counter,
+            amount,
+        }
+    }
+
+    pub fn amount(&self) -> i64 {
+        self.amount
+    }
+}
+
+impl Action for ChangeCounter {
+    /// Fails if there is no counter with the given ID. Reaching a bound is not a failure.
+    fn execute(&self, gs: &mut Box<dyn GameState>) -> bool {
+        let Some(counter) = gs.component_mut::<Counter>(self.counter) else {
+            return false;
+        };
+        counter.increment(self.amount);
+        true
+    }
+
+    /// The name of the counter followed by the change, e.g. "Health -2"
+    fn get_string(&self, gs: &dyn GameState) -> String {
+        let name = gs
+            .component::<Counter>(self.counter)
+            .map_or(format!("counter {}", self.counter), |c| {
+                c.name().to_string()
+            });
+        format!("{} {:+}", name, self.amount)
+    }
+
+    fn id(&self) -> i32 {
+        self.data.id()
+    }
+}
+
+/// Two changes are equal if they have the same effect, whatever their IDs
+impl PartialEq for ChangeCounter {
+    fn eq(&self, other: &Self) -> bool {
+        self.counter == other.counter && self.amount == other.amount
+    }
+}
+
+impl Eq for ChangeCounter {}
+
+impl Hash for ChangeCounter {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.counter.hash(state);
+        self.amount.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        game_parameters::AbstractParameters, game_state::AbstractGameState,
+    };
+
+    #[test]
+    fn test_change_counter() {
+        let mut state = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        state.add_component(Box::new(
+            Counter::new_with_id("Health", 8, 0, 10, 100).unwrap(),
+        ));
+        let mut gs: Box<dyn GameState> = Box::new(state);
+        let mut ids = IdAllocator::new();
+
+        let damage = ChangeCounter::new(100, -3, &mut ids);
+        assert_eq!(damage.get_string(gs.as_ref()), "Health -3");
+        assert!(damage.execute(&mut gs));
+        assert!(damage.execute(&mut gs));
+        assert!(damage.execute(&mut gs));
+        assert_eq!(gs.component::<Counter>(100).unwrap().value(), 0);
+
+        let heal = ChangeCounter::new(100, 2, &mut ids);
+        assert_eq!(heal.get_string(gs.as_ref()), "Health +2");
+        assert!(heal.execute(&mut gs));
+        assert_eq!(gs.component::<Counter>(100).unwrap().value(), 2);
+        assert!(!ChangeCounter::new(999, 1, &mut ids).execute(&mut gs));
+        assert!(heal == ChangeCounter::new(100, 2, &mut ids));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::main_code::core::{
     actions::action::{AbstractAction, Action},
     game_state::GameState,
+    id_allocator::IdAllocator,
 };
 
 #[derive(Clone)]
@@ -9,9 +10,15 @@ pub struct DoNothing {
 }
 
 impl DoNothing {
-    pub fn new() -> Self {
+    pub fn new(ids: &mut IdAllocator) -> Self {
         DoNothing {
-            data: AbstractAction::new(),
+            data: AbstractAction::new(ids),
+        }
+    }
+
+    pub fn with_id(id: i32) -> Self {
+        DoNothing {
+            data: AbstractAction::with_id(id),
         }
     }
 }
@@ -0,0 +1,197 @@
+use crate::main_code::core::{
+    actions::action::{AbstractAction, Action},
+    components::{component::Component, deck::Deck},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    visibility,
+};
+use std::{
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+/// Moves the component at the given index of a deck to the top of another deck, e.g. drawing a
+/// card from the draw pile into a hand. Both decks must be registered in the game state. The
+/// component is given to the owner of the destination deck.
+#[derive(Clone)]
+pub struct DrawComponent<T: Component + Clone> {
+    data: AbstractAction,
+    from_deck: usize,
+    to_deck: usize,
+    index: usize,
+    component: PhantomData<T>,
+}
+
+impl<T: Component + Clone> DrawComponent<T> {
+    pub fn new(from_deck: usize, to_deck: usize, index: usize, ids: &mut IdAllocator) -> Self {
+        Self {
+            data: AbstractAction::new(ids),
+            from_deck,
+            to_deck,
+            index,
+            component: PhantomData,
+        }
+    }
+
+    /// Draws the top component of the deck
+    pub fn from_top(from_deck: usize, to_deck: usize, ids: &mut IdAllocator) -> Self {
+        Self::new(from_deck, to_deck, 0, ids)
+    }
+
+    pub fn from_deck(&self) -> usize {
+        self.from_deck
+    }
+
+    pub fn to_deck(&self) -> usize {
+        self.to_deck
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    fn deck_name(gs: &dyn GameState, deck: usize) -> String {
+        gs.component::<Deck<T>>(deck)
+            .map_or(format!("deck {}", deck), |d| {
+                d.base().component_name().to_string()
+            })
+    }
+
+    // Describes the drawn component with the given text, e.g. its name or '?'
+    fn describe(&self, gs: &dyn GameState, component: String) -> String {
+        format!(
+            "Draw {} from {} to {}",
+            component,
+            Self::deck_name(gs, self.from_deck),
+            Self::deck_name(gs, self.to_deck)
+        )
+    }
+}
+
+impl<T: Component + Clone> Action for DrawComponent<T> {
+    /// Fails without changes if a deck is missing, the index is out of bounds or the target deck
+    /// is full
+    fn execute(&self, gs: &mut Box<dyn GameState>) -> bool {
+        let Some(owner) = gs
+            .component::<Deck<T>>(self.to_deck)
+            .and_then(|d| (!d.is_full()).then(|| d.owner_id()))
+        else {
+            return false;
+        };
+        let Some(mut component) = gs
+            .component_mut::<Deck<T>>(self.from_deck)
+            .and_then(|d| d.remove_at(self.index))
+        else {
+            return false;
+        };
+        gs.transfer_ownership(&mut component, owner);
+        gs.component_mut::<Deck<T>>(self.to_deck)
+            .unwrap()
+            .add(component)
+    }
+
+    fn get_string(&self, gs: &dyn GameState) -> String {
+        let component = gs
+            .component::<Deck<T>>(self.from_deck)
+            .and_then(|d| d.get(self.index))
+            .map_or(format!("component {}", self.index), |c| c.short_string());
+        self.describe(gs, component)
+    }
+
+    /// Players who cannot see the component in the source deck do not know what is drawn
+    fn get_string_perspective(&self, gs: &dyn GameState, perspective_player: i8) -> String {
+        let visible = gs.component::<Deck<T>>(self.from_deck).is_some_and(|d| {
+            visibility::is_visible_at(gs, d, self.index, perspective_player as usize)
+        });
+        if visible {
+            self.get_string(gs)
+        } else {
+            self.describe(gs, "?".to_string())
+        }
+    }
+
+    fn id(&self) -> i32 {
+        self.data.id()
+    }
+}
+
+/// Two draws are equal if they have the same effect, whatever their IDs
+impl<T: Component + Clone> PartialEq for DrawComponent<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.from_deck == other.from_deck
+            && self.to_deck == other.to_deck
+            && self.index == other.index
+    }
+}
+
+impl<T: Component + Clone> Eq for DrawComponent<T> {}
+
+impl<T: Component + Clone> Hash for DrawComponent<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.from_deck.hash(state);
+        self.to_deck.hash(state);
+        self.index.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        components::card::Card, core_constants::VisibilityMode,
+        game_parameters::AbstractParameters, game_state::AbstractGameState,
+        interfaces::component_container::IComponentContainer,
+    };
+
+    fn state() -> Box<dyn GameState> {
+        let mut gs = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let mut pile = Deck::new_with_id("Pile", -1, 100, VisibilityMode::HiddenToAll);
+        pile.add_to_bottom(Card::new_with_id("Fireball", 1));
+        pile.add_to_bottom(Card::new_with_id("Shield", 2));
+        gs.add_component(Box::new(pile));
+        gs.add_component(Box::new(Deck::<Card>::new_with_id(
+            "Hand",
+            0,
+            101,
+            VisibilityMode::VisibleToOwner,
+        )));
+        Box::new(gs)
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut gs = state();
+        let draw = DrawComponent::<Card>::from_top(100, 101, &mut IdAllocator::new());
+        assert_eq!(
+            draw.get_string(gs.as_ref()),
+            "Draw Fireball from Pile to Hand"
+        );
+        assert_eq!(
+            draw.get_string_perspective(gs.as_ref(), 0),
+            "Draw ? from Pile to Hand"
+        );
+
+        assert!(draw.execute(&mut gs));
+        let hand = gs.component::<Deck<Card>>(101).unwrap();
+        assert_eq!(hand.get(0).unwrap().name(), "Fireball");
+        assert_eq!(hand.get(0).unwrap().owner_id(), 0);
+        assert_eq!(gs.ownership_history().len(), 1);
+        assert_eq!(gs.component::<Deck<Card>>(100).unwrap().get_size(), 1);
+
+        assert!(!DrawComponent::<Card>::new(100, 101, 5, &mut IdAllocator::new()).execute(&mut gs));
+        assert!(
+            !DrawComponent::<Card>::from_top(100, 999, &mut IdAllocator::new()).execute(&mut gs)
+        );
+        assert_eq!(gs.component::<Deck<Card>>(100).unwrap().get_size(), 1);
+    }
+
+    #[test]
+    fn test_semantic_equality() {
+        let mut ids = IdAllocator::new();
+        let a = DrawComponent::<Card>::from_top(100, 101, &mut ids);
+        let b = DrawComponent::<Card>::new(100, 101, 0, &mut ids);
+        assert_ne!(a.id(), b.id());
+        assert!(a == b);
+        assert!(a != DrawComponent::<Card>::from_top(101, 100, &mut ids));
+    }
+}
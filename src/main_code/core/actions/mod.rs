@@ -1,2 +1,9 @@
 pub mod action;
+pub mod change_counter;
 pub mod do_nothing;
+pub mod draw_component;
+pub mod move_component;
+pub mod pass_turn;
+pub mod play_card;
+pub mod roll_dice;
+pub mod set_grid_value;
@@ -0,0 +1,163 @@
+use crate::main_code::core::{
+    actions::action::{AbstractAction, Action},
+    components::{area::Area, component::Component},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    visibility,
+};
+use std::hash::{Hash, Hasher};
+
+/// Moves a component, with everything nested inside it, from one area to another, and gives it to
+/// the owner of the destination area. Both areas must be registered in the game state.
+#[derive(Clone)]
+pub struct MoveComponent {
+    data: AbstractAction,
+    from_area: usize,
+    to_area: usize,
+    component_id: usize,
+}
+
+impl MoveComponent {
+    pub fn new(
+        from_area: usize,
+        to_area: usize,
+        component_id: usize,
+        ids: &mut IdAllocator,
+    ) -> Self {
+        Self {
+            data: AbstractAction::new(ids),
+            from_area,
+            to_area,
+            component_id,
+        }
+    }
+
+    pub fn component_id(&self) -> usize {
+        self.component_id
+    }
+
+    fn area_name(gs: &dyn GameState, area: usize) -> String {
+        gs.component::<Area>(area)
+            .map_or(format!("area {}", area), |a| a.name().to_string())
+    }
+
+    // Describes the moved component with the given text, e.g. its name or '?'
+    fn describe(&self, gs: &dyn GameState, component: String) -> String {
+        format!(
+            "Move {} from {} to {}",
+            component,
+            Self::area_name(gs, self.from_area),
+            Self::area_name(gs, self.to_area)
+        )
+    }
+}
+
+impl Action for MoveComponent {
+    /// Fails without changes if an area is missing or the component is not held directly by the
+    /// source area
+    fn execute(&self, gs: &mut Box<dyn GameState>) -> bool {
+        let Some(owner) = gs.component::<Area>(self.to_area).map(|a| a.owner_id()) else {
+            return false;
+        };
+        let Some(mut component) = gs.component_mut::<Area>(self.from_area).and_then(|a| {
+            (a.parent_of(self.component_id) == Some(self.from_area))
+                .then(|| a.remove_component(self.component_id))
+                .flatten()
+        }) else {
+            return false;
+        };
+        gs.transfer_ownership(component.as_mut(), owner);
+        gs.component_mut::<Area>(self.to_area)
+            .unwrap()
+            .put_component(component);
+        true
+    }
+
+    fn get_string(&self, gs: &dyn GameState) -> String {
+        let component = gs
+            .component::<Area>(self.from_area)
+            .and_then(|a| a.get_component(self.component_id))
+            .map_or(format!("component {}", self.component_id), |c| {
+                c.short_string()
+            });
+        self.describe(gs, component)
+    }
+
+    /// Players who cannot see the component in the source area do not know what is moved
+    fn get_string_perspective(&self, gs: &dyn GameState, perspective_player: i8) -> String {
+        let visible = gs.component::<Area>(self.from_area).is_some_and(|a| {
+            visibility::is_visible_to(gs, a, self.component_id, perspective_player as usize)
+        });
+        if visible {
+            self.get_string(gs)
+        } else {
+            self.describe(gs, "?".to_string())
+        }
+    }
+
+    fn id(&self) -> i32 {
+        self.data.id()
+    }
+}
+
+/// Two moves are equal if they have the same effect, whatever their IDs
+impl PartialEq for MoveComponent {
+    fn eq(&self, other: &Self) -> bool {
+        self.from_area == other.from_area
+            && self.to_area == other.to_area
+            && self.component_id == other.component_id
+    }
+}
+
+impl Eq for MoveComponent {}
+
+impl Hash for MoveComponent {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.from_area.hash(state);
+        self.to_area.hash(state);
+        self.component_id.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        components::token::Token, core_constants::VisibilityMode,
+        game_parameters::AbstractParameters, game_state::AbstractGameState,
+        interfaces::component_container::IComponentContainer,
+    };
+
+    #[test]
+    fn test_move_between_areas() {
+        let mut state = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let mut reserve = Area::new_with_id(1, 100);
+        reserve.put_component(Box::new(Token::new_with_id("Meeple", 1)));
+        reserve.set_visibility_mode(VisibilityMode::VisibleToOwner);
+        state.add_component(Box::new(reserve));
+        state.add_component(Box::new(Area::new_with_id(0, 101)));
+        let mut gs: Box<dyn GameState> = Box::new(state);
+
+        let action = MoveComponent::new(100, 101, 1, &mut IdAllocator::new());
+        assert_eq!(
+            action.get_string(gs.as_ref()),
+            "Move Meeple from Area to Area"
+        );
+        assert_eq!(
+            action.get_string_perspective(gs.as_ref(), 0),
+            "Move ? from Area to Area"
+        );
+        assert_eq!(
+            action.get_string_perspective(gs.as_ref(), 1),
+            action.get_string(gs.as_ref())
+        );
+
+        assert!(action.execute(&mut gs));
+        assert_eq!(gs.component::<Area>(100).unwrap().get_size(), 0);
+        let board = gs.component::<Area>(101).unwrap();
+        assert_eq!(board.get_component(1).unwrap().owner_id(), 0);
+        assert_eq!(gs.ownership_history().len(), 1);
+        assert!(!action.execute(&mut gs));
+        assert!(action == MoveComponent::new(100, 101, 1, &mut IdAllocator::starting_at(7)));
+    }
+}
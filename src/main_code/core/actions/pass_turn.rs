@@ -0,0 +1,85 @@
+use crate::main_code::core::{
+    actions::action::{AbstractAction, Action},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+};
+use std::hash::{Hash, Hasher};
+
+/// The player ends their turn, which goes to the next player still in the game.
+#[derive(Clone)]
+pub struct PassTurn {
+    data: AbstractAction,
+    player: usize,
+}
+
+impl PassTurn {
+    pub fn new(player: usize, ids: &mut IdAllocator) -> Self {
+        Self {
+            data: AbstractAction::new(ids),
+            player,
+        }
+    }
+
+    pub fn player(&self) -> usize {
+        self.player
+    }
+}
+
+impl Action for PassTurn {
+    /// Fails if it is not the turn of the player, or no player is left in the game
+    fn execute(&self, gs: &mut Box<dyn GameState>) -> bool {
+        gs.turn_owner() as usize == self.player && gs.end_player_turn()
+    }
+
+    fn get_string(&self, _gs: &dyn GameState) -> String {
+        format!("Player {} passes", self.player)
+    }
+
+    fn id(&self) -> i32 {
+        self.data.id()
+    }
+}
+
+/// Two passes are equal if they are made by the same player, whatever their IDs
+impl PartialEq for PassTurn {
+    fn eq(&self, other: &Self) -> bool {
+        self.player == other.player
+    }
+}
+
+impl Eq for PassTurn {}
+
+impl Hash for PassTurn {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.player.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        core_constants::GameResult, game_parameters::AbstractParameters,
+        game_state::AbstractGameState,
+    };
+
+    #[test]
+    fn test_pass_skips_eliminated_players() {
+        let mut state = AbstractGameState::new(Box::new(AbstractParameters::new()), 3, 0);
+        state.set_player_result(GameResult::LoseGame, 1);
+        let mut gs: Box<dyn GameState> = Box::new(state);
+        let mut ids = IdAllocator::new();
+
+        assert_eq!(
+            PassTurn::new(0, &mut ids).get_string(gs.as_ref()),
+            "Player 0 passes"
+        );
+        assert!(PassTurn::new(0, &mut ids).execute(&mut gs));
+        assert_eq!(gs.turn_owner(), 2);
+        // Only the turn owner can pass
+        assert!(!PassTurn::new(0, &mut ids).execute(&mut gs));
+        assert!(PassTurn::new(2, &mut ids).execute(&mut gs));
+        assert_eq!(gs.turn_owner(), 0);
+        assert!(PassTurn::new(2, &mut ids) == PassTurn::new(2, &mut ids));
+    }
+}
@@ -0,0 +1,157 @@
+use crate::main_code::core::{
+    actions::action::{AbstractAction, Action},
+    components::{card::Card, component::Component, deck::Deck},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+};
+use std::hash::{Hash, Hasher};
+
+/// A player plays a card from their hand onto a deck, e.g. a discard pile or a play area. The
+/// card is identified by its ID, so the action stays valid if the hand is reordered, and is given
+/// to the owner of the target deck. Played cards are revealed, so every player sees the same
+/// description.
+#[derive(Clone)]
+pub struct PlayCard {
+    data: AbstractAction,
+    player: usize,
+    hand: usize,
+    target: usize,
+    card_id: usize,
+}
+
+impl PlayCard {
+    pub fn new(
+        player: usize,
+        hand: usize,
+        target: usize,
+        card_id: usize,
+        ids: &mut IdAllocator,
+    ) -> Self {
+        Self {
+            data: AbstractAction::new(ids),
+            player,
+            hand,
+            target,
+            card_id,
+        }
+    }
+
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    pub fn card_id(&self) -> usize {
+        self.card_id
+    }
+}
+
+impl Action for PlayCard {
+    /// Fails without changes if a deck is missing, the hand is not owned by the player, the card
+    /// is not in the hand or the target deck is full
+    fn execute(&self, gs: &mut Box<dyn GameState>) -> bool {
+        if gs
+            .component::<Deck<Card>>(self.hand)
+            .is_none_or(|d| d.owner_id() != self.player as i32)
+        {
+            return false;
+        }
+        let Some(owner) = gs
+            .component::<Deck<Card>>(self.target)
+            .and_then(|d| (!d.is_full()).then(|| d.owner_id()))
+        else {
+            return false;
+        };
+        let Some(mut card) = gs
+            .component_mut::<Deck<Card>>(self.hand)
+            .and_then(|d| d.remove_by_id(self.card_id))
+        else {
+            return false;
+        };
+        gs.transfer_ownership(&mut card, owner);
+        gs.component_mut::<Deck<Card>>(self.target)
+            .unwrap()
+            .add(card)
+    }
+
+    fn get_string(&self, gs: &dyn GameState) -> String {
+        let card = gs
+            .component::<Deck<Card>>(self.hand)
+            .and_then(|d| d.index_of(self.card_id).and_then(|i| d.get(i)))
+            .map_or(format!("card {}", self.card_id), |c| c.name().to_string());
+        let target = gs
+            .component::<Deck<Card>>(self.target)
+            .map_or(format!("deck {}", self.target), |d| {
+                d.base().component_name().to_string()
+            });
+        format!("Player {} plays {} on {}", self.player, card, target)
+    }
+
+    fn id(&self) -> i32 {
+        self.data.id()
+    }
+}
+
+/// Two plays are equal if they have the same effect, whatever their IDs
+impl PartialEq for PlayCard {
+    fn eq(&self, other: &Self) -> bool {
+        self.player == other.player
+            && self.hand == other.hand
+            && self.target == other.target
+            && self.card_id == other.card_id
+    }
+}
+
+impl Eq for PlayCard {}
+
+impl Hash for PlayCard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.player.hash(state);
+        self.hand.hash(state);
+        self.target.hash(state);
+        self.card_id.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        core_constants::VisibilityMode, game_parameters::AbstractParameters,
+        game_state::AbstractGameState,
+    };
+
+    #[test]
+    fn test_play_card() {
+        let mut state = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let mut hand = Deck::new_with_id("Hand", 1, 100, VisibilityMode::VisibleToOwner);
+        for (name, id) in [("Fireball", 1), ("Shield", 2)] {
+            let mut card = Card::new_with_id(name, id);
+            card.set_owner_id(1);
+            hand.add_to_bottom(card);
+        }
+        state.add_component(Box::new(hand));
+        state.add_component(Box::new(Deck::<Card>::new_with_id(
+            "Discard",
+            -1,
+            101,
+            VisibilityMode::VisibleToAll,
+        )));
+        let mut gs: Box<dyn GameState> = Box::new(state);
+
+        let play = PlayCard::new(1, 100, 101, 2, &mut IdAllocator::new());
+        assert_eq!(
+            play.get_string_perspective(gs.as_ref(), 0),
+            "Player 1 plays Shield on Discard"
+        );
+        // Only the owner of the hand can play from it
+        assert!(!PlayCard::new(0, 100, 101, 2, &mut IdAllocator::new()).execute(&mut gs));
+        assert!(play.execute(&mut gs));
+        let played = gs.component::<Deck<Card>>(101).unwrap().get(0).unwrap();
+        assert_eq!(played.name(), "Shield");
+        assert_eq!(played.owner_id(), -1);
+        assert_eq!(gs.ownership_history().len(), 1);
+        // The card is no longer in the hand
+        assert!(!play.execute(&mut gs));
+        assert!(play == PlayCard::new(1, 100, 101, 2, &mut IdAllocator::starting_at(5)));
+    }
+}
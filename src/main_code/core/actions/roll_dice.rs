@@ -0,0 +1,176 @@
+use crate::main_code::core::{
+    actions::action::{AbstractAction, Action},
+    components::dice::{Dice, DicePool},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use std::hash::{Hash, Hasher};
+
+/// Rolls a single dice or the dice of a pool, using the RNG of the game state. Locked dice are not
+/// rolled. The dice or pool must be registered in the game state.
+#[derive(Clone)]
+pub struct RollDice {
+    data: AbstractAction,
+    dice: usize,
+    // Indices of the dice of the pool to reroll, all of them if None
+    indices: Option<Vec<usize>>,
+}
+
+impl RollDice {
+    /// Rolls the dice, or every dice of the pool
+    pub fn new(dice: usize, ids: &mut IdAllocator) -> Self {
+        Self {
+            data: AbstractAction::new(ids),
+            dice,
+            indices: None,
+        }
+    }
+
+    /// Rerolls only the dice of the pool at the given indices
+    pub fn reroll(pool: usize, mut indices: Vec<usize>, ids: &mut IdAllocator) -> Self {
+        indices.sort();
+        indices.dedup();
+        Self {
+            data: AbstractAction::new(ids),
+            dice: pool,
+            indices: Some(indices),
+        }
+    }
+}
+
+impl Action for RollDice {
+    /// Fails if there is no dice or pool with the given ID, the dice is locked or an index is not
+    /// in the pool. A failed roll does not use the RNG of the game.
+    fn execute(&self, gs: &mut Box<dyn GameState>) -> bool {
+        let valid = match gs.component::<Dice>(self.dice) {
+            Some(dice) => !dice.is_locked(),
+            None => gs.component::<DicePool>(self.dice).is_some_and(|pool| {
+                self.indices
+                    .as_ref()
+                    .is_none_or(|indices| indices.iter().all(|i| *i < pool.len()))
+            }),
+        };
+        if !valid {
+            return false;
+        }
+        // The component and the RNG can't be borrowed from the state at the same time, so the
+        // roll uses an RNG seeded from the one of the game
+        let mut rnd = StdRng::seed_from_u64(gs.rnd().gen());
+        if let Some(dice) = gs.component_mut::<Dice>(self.dice) {
+            return dice.roll(&mut rnd);
+        }
+        let pool = gs.component_mut::<DicePool>(self.dice).unwrap();
+        match &self.indices {
+            None => {
+                pool.roll_all(&mut rnd);
+                true
+            }
+            Some(indices) => pool.reroll(indices, &mut rnd).is_some(),
+        }
+    }
+
+    fn get_string(&self, gs: &dyn GameState) -> String {
+        let roller = match gs.get_component(self.dice).map(|c| c.owner_id()) {
+            Some(owner) if owner >= 0 => format!("Player {} rolls", owner),
+            _ => "Roll".to_string(),
+        };
+        if let Some(dice) = gs.component::<Dice>(self.dice) {
+            return format!("{} {}", roller, dice.name());
+        }
+        match &self.indices {
+            None => format!("{} all dice", roller),
+            Some(indices) => {
+                let indices: Vec<String> = indices.iter().map(|i| i.to_string()).collect();
+                format!("{} dice {}", roller, indices.join(", "))
+            }
+        }
+    }
+
+    fn id(&self) -> i32 {
+        self.data.id()
+    }
+}
+
+/// Two rolls are equal if they roll the same dice, whatever their IDs
+impl PartialEq for RollDice {
+    fn eq(&self, other: &Self) -> bool {
+        self.dice == other.dice && self.indices == other.indices
+    }
+}
+
+impl Eq for RollDice {}
+
+impl Hash for RollDice {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.dice.hash(state);
+        self.indices.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        game_parameters::AbstractParameters, game_state::AbstractGameState,
+    };
+
+    fn state(seed: u64) -> Box<dyn GameState> {
+        let mut state = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, seed);
+        let mut pool = DicePool::new_with_id(1, 100);
+        for id in 1..=5 {
+            pool.add(Dice::new_with_id(6, id));
+        }
+        pool.get_mut(0).unwrap().lock();
+        state.add_component(Box::new(pool));
+        state.add_component(Box::new(Dice::new_with_id(20, 50)));
+        Box::new(state)
+    }
+
+    fn faces(gs: &dyn GameState) -> Vec<usize> {
+        let pool = gs.component::<DicePool>(100).unwrap();
+        pool.dice().iter().map(|d| d.face_index()).collect()
+    }
+
+    #[test]
+    fn test_rolls_are_seeded() {
+        let mut ids = IdAllocator::new();
+        let roll = RollDice::new(100, &mut ids);
+        let mut gs = state(3);
+        let mut same_seed = state(3);
+        for _ in 0..5 {
+            assert!(roll.execute(&mut gs));
+            assert!(roll.execute(&mut same_seed));
+            assert_eq!(faces(gs.as_ref()), faces(same_seed.as_ref()));
+            // The locked dice is never rolled
+            assert_eq!(faces(gs.as_ref())[0], 0);
+        }
+        assert_eq!(roll.get_string(gs.as_ref()), "Player 1 rolls all dice");
+
+        let reroll = RollDice::reroll(100, vec![3, 1, 3], &mut ids);
+        assert_eq!(reroll.get_string(gs.as_ref()), "Player 1 rolls dice 1, 3");
+        let before = faces(gs.as_ref());
+        assert!(reroll.execute(&mut gs));
+        let after = faces(gs.as_ref());
+        assert_eq!((before[2], before[4]), (after[2], after[4]));
+        assert!(reroll == RollDice::reroll(100, vec![1, 3], &mut ids));
+
+        let d20 = RollDice::new(50, &mut ids);
+        assert_eq!(d20.get_string(gs.as_ref()), "Roll d20");
+        assert!(d20.execute(&mut gs));
+        assert!(!RollDice::new(999, &mut ids).execute(&mut gs));
+        assert!(!RollDice::reroll(100, vec![5], &mut ids).execute(&mut gs));
+    }
+
+    #[test]
+    fn test_failed_rolls_keep_the_rng() {
+        let mut ids = IdAllocator::new();
+        let mut gs = state(3);
+        let mut untouched = state(3);
+        gs.component_mut::<Dice>(50).unwrap().lock();
+        assert!(!RollDice::new(50, &mut ids).execute(&mut gs));
+        assert!(!RollDice::new(999, &mut ids).execute(&mut gs));
+        assert!(!RollDice::reroll(100, vec![1, 5], &mut ids).execute(&mut gs));
+        assert_eq!(gs.rnd().gen::<u64>(), untouched.rnd().gen::<u64>());
+    }
+}
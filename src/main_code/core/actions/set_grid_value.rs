@@ -0,0 +1,120 @@
+use crate::main_code::core::{
+    actions::action::{AbstractAction, Action},
+    components::{component::Component, grid_board::GridBoard},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+};
+use std::hash::{Hash, Hasher};
+
+/// Puts a component in a cell of a grid board, replacing the one there if any, e.g. placing a
+/// piece in tic-tac-toe. The board must be registered in the game state.
+#[derive(Clone)]
+pub struct SetGridValue<T: Component + Clone> {
+    data: AbstractAction,
+    grid: usize,
+    x: usize,
+    y: usize,
+    value: T,
+}
+
+impl<T: Component + Clone> SetGridValue<T> {
+    pub fn new(grid: usize, x: usize, y: usize, value: T, ids: &mut IdAllocator) -> Self {
+        Self {
+            data: AbstractAction::new(ids),
+            grid,
+            x,
+            y,
+            value,
+        }
+    }
+
+    pub fn position(&self) -> (usize, usize) {
+        (self.x, self.y)
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Component + Clone> Action for SetGridValue<T> {
+    /// Fails if the board is missing or the cell is out of bounds
+    fn execute(&self, gs: &mut Box<dyn GameState>) -> bool {
+        gs.component_mut::<GridBoard<T>>(self.grid)
+            .is_some_and(|g| g.set(self.x, self.y, self.value.clone()))
+    }
+
+    fn get_string(&self, _gs: &dyn GameState) -> String {
+        format!(
+            "Set ({}, {}) to {}",
+            self.x,
+            self.y,
+            self.value.short_string()
+        )
+    }
+
+    fn id(&self) -> i32 {
+        self.data.id()
+    }
+}
+
+/// Two actions are equal if they put the same kind of component (same type, owner, name and
+/// properties, whatever its ID) in the same cell
+impl<T: Component + Clone> PartialEq for SetGridValue<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let (value, other_value) = (self.value.base(), other.value.base());
+        self.grid == other.grid
+            && self.position() == other.position()
+            && value.component_type() == other_value.component_type()
+            && value.owner_id() == other_value.owner_id()
+            && value.component_name() == other_value.component_name()
+            && value.properties() == other_value.properties()
+    }
+}
+
+impl<T: Component + Clone> Eq for SetGridValue<T> {}
+
+impl<T: Component + Clone> Hash for SetGridValue<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let value = self.value.base();
+        self.grid.hash(state);
+        self.position().hash(state);
+        value.component_type().hash(state);
+        value.owner_id().hash(state);
+        value.component_name().hash(state);
+        value.properties().hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        components::token::Token, game_parameters::AbstractParameters,
+        game_state::AbstractGameState,
+    };
+
+    #[test]
+    fn test_set_grid_value() {
+        let mut state = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        state.add_component(Box::new(GridBoard::<Token>::new_with_id(3, 3, 100)));
+        let mut gs: Box<dyn GameState> = Box::new(state);
+        let mut ids = IdAllocator::new();
+
+        let action = SetGridValue::new(100, 2, 1, Token::new_with_id("X", 1), &mut ids);
+        assert_eq!(action.get_string(gs.as_ref()), "Set (2, 1) to X");
+        assert!(action.execute(&mut gs));
+        let board = gs.component::<GridBoard<Token>>(100).unwrap();
+        assert_eq!(board.get(2, 1).unwrap().token_type(), "X");
+        assert!(
+            !SetGridValue::new(100, 3, 0, Token::new_with_id("X", 2), &mut ids).execute(&mut gs)
+        );
+
+        // Another X token in the same cell is the same move
+        assert!(action == SetGridValue::new(100, 2, 1, Token::new_with_id("X", 3), &mut ids));
+        assert!(action != SetGridValue::new(100, 2, 1, Token::new_with_id("O", 4), &mut ids));
+        let mut owned = Token::new_with_id("X", 5);
+        owned.set_owner_id(0);
+        assert!(action != SetGridValue::new(100, 2, 1, owned, &mut ids));
+    }
+}
@@ -1,26 +1,37 @@
 use crate::main_code::core::{
     components::component::{BaseComponent, Component},
     core_constants::{ComponentType, VisibilityMode},
-    interfaces::component_container::IComponentContainer,
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    interfaces::{component_container::IComponentContainer, renderable::IRenderable},
+    visibility,
 };
+use rand::RngCore;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet},
     hash::{Hash, Hasher},
 };
 
 #[derive(Debug, Clone)]
 pub struct Area {
     base: BaseComponent,
-    components: HashMap<usize, Box<dyn Component>>,
+    // Every component in the area, including those nested inside other components, by ID so
+    // that iteration is deterministic
+    components: BTreeMap<usize, Box<dyn Component>>,
+    // Direct container of each component (the area itself for top-level components)
+    parents: BTreeMap<usize, usize>,
+    visibility_mode: VisibilityMode,
 }
 
 impl Area {
-    pub fn new(owner: i32) -> Self {
-        let mut base = BaseComponent::new_with_name(ComponentType::Area, "");
+    pub fn new(owner: i32, ids: &mut IdAllocator) -> Self {
+        let mut base = BaseComponent::new_with_name(ComponentType::Area, "", ids);
         base.set_owner_id(owner);
         Self {
             base,
-            components: HashMap::default(),
+            components: BTreeMap::default(),
+            parents: BTreeMap::default(),
+            visibility_mode: VisibilityMode::VisibleToAll,
         }
     }
 
@@ -29,31 +40,41 @@ impl Area {
         base.set_owner_id(owner);
         Self {
             base,
-            components: HashMap::default(),
+            components: BTreeMap::default(),
+            parents: BTreeMap::default(),
+            visibility_mode: VisibilityMode::VisibleToAll,
         }
     }
 
     pub fn clear(&mut self) {
-        self.components.clear()
+        self.components.clear();
+        self.parents.clear();
     }
 
-    pub fn components_map(&self) -> HashMap<usize, Box<dyn Component>> {
+    pub fn components_map(&self) -> BTreeMap<usize, Box<dyn Component>> {
         self.components.clone()
     }
 
-    pub fn nested_keys(&self) -> HashSet<&usize> {
+    pub fn nested_keys(&self) -> BTreeSet<&usize> {
         self.components.keys().collect()
     }
 
-    pub fn get_component(&self, key: usize) -> Option<&Box<dyn Component>> {
-        self.components.get(&key)
+    pub fn get_component(&self, key: usize) -> Option<&dyn Component> {
+        self.components.get(&key).map(|c| c.as_ref())
     }
 
+    /// Puts a component at the top level of the area, together with everything nested in it.
+    /// Returns the component with the same ID that was in the area, if any. If that component
+    /// cannot be removed (see remove_component), the area is left unchanged and the given
+    /// component is returned instead.
     pub fn put_component(&mut self, c: Box<dyn Component>) -> Option<Box<dyn Component>> {
-        for nc in c.nested_components() {
-            self.components.insert(nc.component_id(), nc.clone());
+        if self.components.contains_key(&c.component_id()) && !self.held_by_areas(c.component_id())
+        {
+            return Some(c);
         }
-        self.components.insert(c.component_id(), c)
+        let previous = self.remove_component(c.component_id());
+        self.insert_under(c, self.component_id());
+        previous
     }
 
     pub fn put_components(&mut self, components: Vec<Box<dyn Component>>) {
@@ -61,25 +82,293 @@ impl Area {
             self.put_component(c);
         }
     }
+
+    /// Removes a component and everything nested in it, from this area and from any inner
+    /// Area that holds it. Returns None if the component is missing, or is inside a container
+    /// other than an Area (e.g. a card in a Deck), as that container could not be updated.
+    pub fn remove_component(&mut self, component_id: usize) -> Option<Box<dyn Component>> {
+        if !self.components.contains_key(&component_id) || !self.held_by_areas(component_id) {
+            return None;
+        }
+        for ancestor in self.ancestors(component_id) {
+            if let Some(area) = self.inner_area_mut(ancestor) {
+                area.remove_component(component_id);
+            }
+        }
+        for descendant in self.descendants(component_id) {
+            self.components.remove(&descendant);
+            self.parents.remove(&descendant);
+        }
+        self.parents.remove(&component_id);
+        self.components.remove(&component_id)
+    }
+
+    /// Moves a component, with everything nested in it, into another container: this area or
+    /// an Area nested in it. Returns false if either is missing, if either is inside a container
+    /// other than an Area, or if the destination is the component itself or lies inside it.
+    pub fn move_component(&mut self, component_id: usize, to: usize) -> bool {
+        let valid_destination =
+            to == self.component_id() || (self.inner_area(to).is_some() && self.held_by_areas(to));
+        if !self.components.contains_key(&component_id)
+            || !self.held_by_areas(component_id)
+            || !valid_destination
+            || to == component_id
+            || self.descendants(component_id).contains(&to)
+        {
+            return false;
+        }
+        if let Some(c) = self.remove_component(component_id) {
+            self.insert_under(c, to);
+        }
+        true
+    }
+
+    /// Copy of the area as the player could believe it to be: the components held directly by
+    /// the area that the player can't see (see visibility::is_visible_to) are left out, with
+    /// everything nested in them, and the visible ones are replaced by their own perspective
+    /// copies (e.g. a hand of cards seen by another player is redeterminised).
+    pub fn copy_for_player(
+        &self,
+        gs: &dyn GameState,
+        player: usize,
+        rnd: &mut dyn RngCore,
+    ) -> Self {
+        let mut copy = Self {
+            base: self.base.clone(),
+            components: BTreeMap::default(),
+            parents: BTreeMap::default(),
+            visibility_mode: self.visibility_mode.clone(),
+        };
+        for c in self.get_components() {
+            if visibility::is_visible_to(gs, self, c.component_id(), player) {
+                let seen = c.perspective_copy(gs, player, rnd).unwrap_or(c);
+                copy.put_component(seen);
+            }
+        }
+        copy
+    }
+
+    /// Direct container of a component: this area's ID for top-level components
+    pub fn parent_of(&self, component_id: usize) -> Option<usize> {
+        self.parents.get(&component_id).copied()
+    }
+
+    /// Components held directly by a container (this area or a nested one), sorted by ID
+    pub fn children_of(&self, container_id: usize) -> Vec<usize> {
+        self.parents
+            .iter()
+            .filter(|(_, p)| **p == container_id)
+            .map(|(c, _)| *c)
+            .collect()
+    }
+
+    /// IDs of the containers from this area down to the component, both included
+    pub fn path_to(&self, component_id: usize) -> Option<Vec<usize>> {
+        if !self.components.contains_key(&component_id) {
+            return None;
+        }
+        let mut path = self.ancestors(component_id);
+        path.reverse();
+        path.insert(0, self.component_id());
+        path.push(component_id);
+        Some(path)
+    }
+
+    /// Containers of a component inside this area, from its direct parent outwards. The area
+    /// itself is not included.
+    fn ancestors(&self, component_id: usize) -> Vec<usize> {
+        let mut ancestors = vec![];
+        let mut current = component_id;
+        while let Some(parent) = self.parent_of(current) {
+            if parent == self.component_id() {
+                break;
+            }
+            ancestors.push(parent);
+            current = parent;
+        }
+        ancestors
+    }
+
+    // True if every container of the component inside this area is an Area, so that all their
+    // copies can be kept up to date
+    fn held_by_areas(&self, component_id: usize) -> bool {
+        self.ancestors(component_id)
+            .iter()
+            .all(|a| self.inner_area(*a).is_some())
+    }
+
+    fn descendants(&self, component_id: usize) -> Vec<usize> {
+        let mut descendants = vec![];
+        let mut pending = vec![component_id];
+        while let Some(current) = pending.pop() {
+            let children = self.children_of(current);
+            descendants.extend(children.iter().copied());
+            pending.extend(children);
+        }
+        descendants
+    }
+
+    fn inner_area(&self, component_id: usize) -> Option<&Area> {
+        self.components
+            .get(&component_id)
+            .and_then(|c| c.downcast_ref::<Area>())
+    }
+
+    fn inner_area_mut(&mut self, component_id: usize) -> Option<&mut Area> {
+        self.components
+            .get_mut(&component_id)
+            .and_then(|c| c.downcast_mut::<Area>())
+    }
+
+    /// Inserts a component under the given container, keeping the copies of the inner Areas
+    /// that contain it up to date
+    fn insert_under(&mut self, c: Box<dyn Component>, parent: usize) {
+        if parent != self.component_id() {
+            let mut containers = self.ancestors(parent);
+            containers.insert(0, parent);
+            for container in containers {
+                if let Some(area) = self.inner_area_mut(container) {
+                    area.insert_under(c.clone(), parent);
+                }
+            }
+        }
+        self.record_parents(c.as_ref(), parent);
+        for nc in c.nested_components() {
+            self.components.insert(nc.component_id(), nc);
+        }
+        self.components.insert(c.component_id(), c);
+    }
+
+    fn record_parents(&mut self, c: &dyn Component, parent: usize) {
+        self.parents.insert(c.component_id(), parent);
+        let nested = c.nested_components();
+        // Direct children are the nested components that are not inside another nested one
+        let deeper: BTreeSet<usize> = nested
+            .iter()
+            .flat_map(|n| n.nested_components())
+            .map(|n| n.component_id())
+            .collect();
+        for n in nested
+            .iter()
+            .filter(|n| !deeper.contains(&n.component_id()))
+        {
+            self.record_parents(n.as_ref(), c.component_id());
+        }
+    }
 }
 
 impl Component for Area {
-    fn component_id(&self) -> usize {
-        self.base.component_id()
+    fn base(&self) -> &BaseComponent {
+        &self.base
     }
 
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn perspective_copy(
+        &self,
+        gs: &dyn GameState,
+        player: usize,
+        rnd: &mut dyn RngCore,
+    ) -> Option<Box<dyn Component>> {
+        Some(Box::new(self.copy_for_player(gs, player, rnd)))
+    }
+
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        Some(self)
+    }
+
+    /// Every component in the area, nested ones included, in ID order
     fn nested_components(&self) -> Vec<Box<dyn Component>> {
-        self.get_components()
+        self.components.values().cloned().collect()
     }
 }
 
 impl IComponentContainer for Area {
+    /// Components held directly by the area, in ID order. Nested ones are reached through their
+    /// containers or nested_components, as for any other container: a card in a Deck in the
+    /// area is one of the Deck's components, not one of the area's, and its position (e.g. for
+    /// FirstVisibleToAll) is counted among its siblings only.
     fn get_components(&self) -> Vec<Box<dyn Component>> {
-        self.components.values().cloned().collect()
+        self.children_of(self.component_id())
+            .iter()
+            .map(|id| self.components[id].clone())
+            .collect()
     }
 
     fn get_visibility_mode(&self) -> VisibilityMode {
-        VisibilityMode::VisibleToAll
+        self.visibility_mode.clone()
+    }
+
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode) {
+        self.visibility_mode = visibility_mode;
+    }
+
+    /// Also updates the copies held by the containers of the component inside the area, whatever
+    /// their type, so that every copy keeps the same owner.
+    fn set_owner_of(&mut self, component_id: usize, owner_id: i32) -> Option<i32> {
+        let previous = self.components.get(&component_id)?.owner_id();
+        for ancestor in self.ancestors(component_id) {
+            if let Some(container) = self
+                .components
+                .get_mut(&ancestor)
+                .and_then(|c| c.as_container_mut())
+            {
+                container.set_owner_of(component_id, owner_id);
+            }
+        }
+        self.components
+            .get_mut(&component_id)?
+            .set_owner_id(owner_id);
+        Some(previous)
+    }
+}
+
+impl Area {
+    /// Name of the area, "Area" if it has none
+    pub fn name(&self) -> &str {
+        match self.base.component_name() {
+            "" => "Area",
+            name => name,
+        }
+    }
+
+    // Renders the components held directly by the area, with their index in get_components;
+    // nested ones are rendered by their containers
+    fn render(&self, render: impl Fn(usize, &dyn Component) -> String) -> String {
+        let top_level: Vec<String> = self
+            .children_of(self.component_id())
+            .iter()
+            .enumerate()
+            .map(|(i, id)| render(i, self.components[id].as_ref()))
+            .collect();
+        format!("{}: [{}]", self.name(), top_level.join(", "))
+    }
+}
+
+impl IRenderable for Area {
+    /// Name and number of components held directly, e.g. "Area (3)"
+    fn short_string(&self) -> String {
+        format!("{} ({})", self.name(), self.get_size())
+    }
+
+    fn detailed_string(&self) -> String {
+        self.render(|_, c| c.detailed_string())
+    }
+
+    fn string_for_player(&self, gs: &dyn GameState, player: usize) -> String {
+        self.render(|i, c| {
+            if visibility::is_visible_at(gs, self, i, player) {
+                c.string_for_player(gs, player)
+            } else {
+                "?".to_string()
+            }
+        })
     }
 }
 
@@ -102,7 +391,12 @@ impl Eq for Area {}
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::main_code::core::components::token::Token;
+    use crate::main_code::core::{
+        components::{deck::Deck, token::Token},
+        game_parameters::AbstractParameters,
+        game_state::AbstractGameState,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
 
     #[test]
     fn test_area_partial_eq() {
@@ -196,7 +490,8 @@ mod tests {
             Box::new(a2.clone()),
             Box::new(c7.clone()),
         ]);
-        assert_eq!(a3.get_size(), 9);
+        assert_eq!(a3.get_size(), 3);
+        assert_eq!(a3.nested_components().len(), 9);
 
         let a3c1 = a3.get_component(1).unwrap();
         let a3c2 = a3.get_component(2).unwrap();
@@ -240,4 +535,194 @@ mod tests {
         assert!(a3a2.get_component(5).is_some());
         assert!(a3a2.get_component(6).is_some());
     }
+
+    fn nested_area() -> Area {
+        let mut a1 = Area::new_with_id(-1, 10);
+        a1.put_components(vec![
+            Box::new(Token::new_with_id("Meeple1", 1)),
+            Box::new(Token::new_with_id("Meeple2", 2)),
+        ]);
+        let mut a2 = Area::new_with_id(-1, 20);
+        a2.put_components(vec![
+            Box::new(Token::new_with_id("Meeple4", 4)),
+            Box::new(Token::new_with_id("Meeple5", 5)),
+        ]);
+        let mut a3 = Area::new_with_id(-1, 30);
+        a3.put_components(vec![
+            Box::new(a1),
+            Box::new(a2),
+            Box::new(Token::new_with_id("Meeple7", 7)),
+        ]);
+        a3
+    }
+
+    fn inner(area: &Area, id: usize) -> &Area {
+        area.get_component(id)
+            .unwrap()
+            .downcast_ref::<Area>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_copy_for_player() {
+        let gs = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let mut area = nested_area();
+        area.set_visibility_mode(VisibilityMode::FirstVisibleToAll);
+        let mut rnd = StdRng::seed_from_u64(1);
+
+        // Only the first component held directly, token 7, is visible
+        let copy = area.copy_for_player(&gs, 1, &mut rnd);
+        assert_eq!(copy.get_size(), 1);
+        assert!(copy.get_component(7).is_some());
+        assert!(copy.get_component(1).is_none());
+
+        // A hidden inner area is copied without its contents
+        area.set_visibility_mode(VisibilityMode::VisibleToAll);
+        let mut hidden = inner(&area, 20).clone();
+        hidden.set_visibility_mode(VisibilityMode::HiddenToAll);
+        area.put_component(Box::new(hidden));
+        let copy = area.copy_for_player(&gs, 0, &mut rnd);
+        assert_eq!(copy.get_size(), 3);
+        assert_eq!(copy.path_to(2), Some(vec![30, 10, 2]));
+        assert_eq!(inner(&copy, 20).get_size(), 0);
+        assert!(copy.get_component(4).is_none());
+    }
+
+    #[test]
+    fn test_components_in_id_order() {
+        let mut area = Area::new_with_id(-1, 10);
+        for id in [5, 3, 9, 1, 7] {
+            area.put_component(Box::new(Token::new_with_id("Meeple", id)));
+        }
+        let ids: Vec<usize> = area
+            .get_components()
+            .iter()
+            .map(|c| c.component_id())
+            .collect();
+        assert_eq!(ids, vec![1, 3, 5, 7, 9]);
+        assert_eq!(area.components_map().keys().next(), Some(&1));
+
+        let nested = nested_area();
+        let ids: Vec<usize> = nested
+            .nested_components()
+            .iter()
+            .map(|c| c.component_id())
+            .collect();
+        assert_eq!(ids, vec![1, 2, 4, 5, 7, 10, 20]);
+    }
+
+    #[test]
+    fn test_parents_and_paths() {
+        let area = nested_area();
+        assert_eq!(area.parent_of(7), Some(30));
+        assert_eq!(area.parent_of(4), Some(20));
+        assert_eq!(area.parent_of(99), None);
+        assert_eq!(area.children_of(30), vec![7, 10, 20]);
+        assert_eq!(area.children_of(10), vec![1, 2]);
+        assert_eq!(area.path_to(5), Some(vec![30, 20, 5]));
+        assert_eq!(area.path_to(99), None);
+    }
+
+    #[test]
+    fn test_remove_component() {
+        let mut area = nested_area();
+        assert_eq!(area.nested_components().len(), 7);
+
+        // Removing an inner area takes its contents with it
+        let removed = area.remove_component(20).unwrap();
+        assert_eq!(removed.downcast_ref::<Area>().unwrap().get_size(), 2);
+        assert_eq!(area.nested_components().len(), 4);
+        assert!(area.get_component(4).is_none());
+        assert_eq!(area.parent_of(4), None);
+
+        // Removing a nested token also updates the inner area holding it
+        assert!(area.remove_component(1).is_some());
+        assert_eq!(area.nested_components().len(), 3);
+        assert_eq!(inner(&area, 10).get_size(), 1);
+        assert!(area.remove_component(1).is_none());
+    }
+
+    #[test]
+    fn test_move_component() {
+        let mut area = nested_area();
+
+        assert!(area.move_component(7, 20));
+        assert_eq!(area.path_to(7), Some(vec![30, 20, 7]));
+        assert!(inner(&area, 20).get_component(7).is_some());
+
+        // Moving an area into another one keeps its contents attached
+        assert!(area.move_component(20, 10));
+        assert_eq!(area.path_to(7), Some(vec![30, 10, 20, 7]));
+        assert_eq!(area.nested_components().len(), 7);
+        assert_eq!(inner(&area, 10).get_size(), 3);
+        assert_eq!(inner(&area, 10).nested_components().len(), 6);
+        assert_eq!(inner(inner(&area, 10), 20).get_size(), 3);
+
+        // An area cannot be moved inside itself, and tokens are not containers
+        assert!(!area.move_component(10, 20));
+        assert!(!area.move_component(1, 2));
+        assert!(!area.move_component(99, 30));
+
+        assert!(area.move_component(4, 30));
+        assert_eq!(area.parent_of(4), Some(30));
+        assert_eq!(inner(&area, 20).get_size(), 2);
+        assert_eq!(inner(&area, 10).nested_components().len(), 5);
+    }
+
+    #[test]
+    fn test_components_in_a_deck_stay_there() {
+        let mut deck = Deck::new_with_id("Deck", -1, 10, VisibilityMode::VisibleToAll);
+        deck.add_to_bottom(Token::new_with_id("Card", 1));
+        deck.add_to_bottom(Token::new_with_id("Card", 2));
+        let mut area = Area::new_with_id(-1, 100);
+        area.put_component(Box::new(deck));
+        area.put_component(Box::new(Area::new_with_id(-1, 20)));
+        assert_eq!(area.parent_of(1), Some(10));
+
+        // The Deck copy can't be updated, so its cards can't be taken out of it
+        assert!(area.remove_component(1).is_none());
+        assert!(!area.move_component(1, 20));
+        let replacement = area.put_component(Box::new(Token::new_with_id("Card", 1)));
+        assert_eq!(replacement.unwrap().component_id(), 1);
+        assert_eq!(area.nested_components().len(), 4);
+        assert_eq!(area.parent_of(1), Some(10));
+        let deck = area.get_component(10).unwrap();
+        assert_eq!(deck.downcast_ref::<Deck<Token>>().unwrap().get_size(), 2);
+
+        // The Deck itself moves with its cards
+        assert!(area.move_component(10, 20));
+        assert_eq!(area.path_to(2), Some(vec![100, 20, 10, 2]));
+        assert_eq!(inner(&area, 20).get_size(), 1);
+        assert_eq!(inner(&area, 20).nested_components().len(), 3);
+    }
+
+    #[test]
+    fn test_rendering() {
+        let gs = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let mut hand = Deck::new_with_id("Hand", 1, 10, VisibilityMode::VisibleToOwner);
+        hand.add_to_bottom(Token::new_with_id("Gold", 1));
+        hand.add_to_bottom(Token::new_with_id("Gold", 2));
+        let mut area = Area::new_with_id(1, 100);
+        area.put_component(Box::new(hand));
+        area.put_component(Box::new(Token::new_with_id("Meeple", 3)));
+
+        assert_eq!(area.short_string(), "Area (2)");
+        assert_eq!(area.detailed_string(), "Area: [Meeple, Hand: [Gold, Gold]]");
+        // Nested containers hide their own contents
+        assert_eq!(
+            area.string_for_player(&gs, 0),
+            "Area: [Meeple, Hand: [?, ?]]"
+        );
+        assert_eq!(area.string_for_player(&gs, 1), area.detailed_string());
+        area.set_visibility_mode(VisibilityMode::HiddenToAll);
+        assert_eq!(area.string_for_player(&gs, 1), "Area: [?, ?]");
+        // Positions only count the components held directly
+        area.set_visibility_mode(VisibilityMode::FirstVisibleToAll);
+        assert_eq!(area.string_for_player(&gs, 1), "Area: [Meeple, ?]");
+        assert_eq!(visibility::hidden_indices(&gs, &area, 1), vec![1]);
+        assert_eq!(
+            format!("{:?}", area.get_component(3).unwrap()),
+            "[Token 3: Meeple]"
+        );
+    }
 }
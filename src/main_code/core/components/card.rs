@@ -0,0 +1,114 @@
+use crate::main_code::core::{
+    components::component::{BaseComponent, Component},
+    core_constants::ComponentType,
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    interfaces::{printable::IPrintable, renderable::IRenderable},
+    properties::{Properties, PropertyKey, PropertyValue},
+};
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Card {
+    base: BaseComponent,
+}
+
+impl Card {
+    pub fn new(name: &str, ids: &mut IdAllocator) -> Self {
+        Self {
+            base: BaseComponent::new_with_name(ComponentType::Card, name, ids),
+        }
+    }
+
+    pub fn new_with_id(name: &str, id: usize) -> Self {
+        Self {
+            base: BaseComponent::new_with_name_and_id(ComponentType::Card, name, id),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.base.component_name()
+    }
+
+    pub fn properties(&self) -> &Properties {
+        self.base.properties()
+    }
+
+    pub fn get_property(&self, key: impl Into<PropertyKey>) -> Option<&PropertyValue> {
+        self.base.get_property(key)
+    }
+
+    pub fn set_property(&mut self, name: &str, value: PropertyValue) -> Option<PropertyValue> {
+        self.base.set_property(name, value)
+    }
+
+    /// Builder-style variant of set_property, useful when declaring cards
+    pub fn with_property(mut self, name: &str, value: PropertyValue) -> Self {
+        self.base.set_property(name, value);
+        self
+    }
+}
+
+impl Component for Card {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        vec![]
+    }
+}
+
+impl IRenderable for Card {
+    fn short_string(&self) -> String {
+        self.name().to_string()
+    }
+
+    fn detailed_string(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.properties().is_empty() {
+            write!(f, "{}", self.name())
+        } else {
+            write!(f, "{} {}", self.name(), self.properties())
+        }
+    }
+}
+
+impl<TGameState: GameState> IPrintable<TGameState> for Card {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_typed_properties() {
+        let mut card = Card::new_with_id("Fireball", 1)
+            .with_property("cost", 3.into())
+            .with_property("colour", "Red".into())
+            .with_property("targets", vec![1, 2].into());
+        card.set_owner_id(2);
+
+        assert_eq!(card.name(), "Fireball");
+        assert_eq!(card.owner_id(), 2);
+        assert_eq!(card.properties().get_int("cost"), Some(3));
+        assert_eq!(card.properties().get_text("colour"), Some("Red"));
+        assert_eq!(card.properties().get_bool("colour"), None);
+        assert_eq!(card.properties().get_list("targets").unwrap().len(), 2);
+
+        assert_eq!(card.set_property("cost", 4.into()), Some(3.into()));
+        assert_eq!(
+            card.to_string(),
+            "Fireball {colour: Red, cost: 4, targets: [1, 2]}"
+        );
+        assert_eq!(Card::new_with_id("Ice", 2).to_string(), "Ice");
+    }
+}
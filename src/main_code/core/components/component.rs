@@ -1,25 +1,75 @@
-use crate::main_code::core::core_constants::ComponentType;
+use crate::main_code::core::{
+    core_constants::ComponentType,
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    interfaces::{component_container::IComponentContainer, renderable::IRenderable},
+    properties::{Properties, PropertyKey, PropertyValue},
+};
 use downcast_rs;
 use dyn_clone;
+use rand::RngCore;
 use std::{
     fmt,
     hash::{Hash, Hasher},
-    sync::atomic::{AtomicUsize, Ordering},
 };
 
-// Atomic counter for unique component IDs
-static GLOBAL_ID: AtomicUsize = AtomicUsize::new(0);
-
-pub trait Component: dyn_clone::DynClone + downcast_rs::Downcast {
-    fn component_id(&self) -> usize;
+pub trait Component: dyn_clone::DynClone + downcast_rs::Downcast + IRenderable {
+    /// ID, type, owner, name and properties of the component
+    fn base(&self) -> &BaseComponent;
+    fn base_mut(&mut self) -> &mut BaseComponent;
     fn nested_components(&self) -> Vec<Box<dyn Component>>;
+
+    fn component_id(&self) -> usize {
+        self.base().component_id
+    }
+
+    fn component_type(&self) -> ComponentType {
+        self.base().component_type()
+    }
+
+    /// ID of the player owning this component, or -1 if it belongs to the game
+    fn owner_id(&self) -> i32 {
+        self.base().owner_id()
+    }
+
+    /// Changes the owner without recording it. Use GameState::transfer_ownership during a game.
+    fn set_owner_id(&mut self, owner_id: i32) {
+        self.base_mut().set_owner_id(owner_id);
+    }
+
+    /// This component as a container of other components, if it is one
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        None
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        None
+    }
+
+    /// Copy of this component as the player could believe it to be, leaving out or
+    /// redeterminising the contents the player can't see (see visibility::is_visible_to).
+    /// Returns None if the component has nothing to hide, so a plain clone will do.
+    fn perspective_copy(
+        &self,
+        _gs: &dyn GameState,
+        _player: usize,
+        _rnd: &mut dyn RngCore,
+    ) -> Option<Box<dyn Component>> {
+        None
+    }
 }
 dyn_clone::clone_trait_object!(Component);
 downcast_rs::impl_downcast!(Component);
 
 impl fmt::Debug for dyn Component {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[impl Component: {}]", self.component_id())
+        write!(
+            f,
+            "[{} {}: {}]",
+            self.component_type(),
+            self.component_id(),
+            self.short_string()
+        )
     }
 }
 
@@ -33,26 +83,30 @@ pub struct BaseComponent {
     owner_id: i32,
     // Name of this component
     component_name: String,
+    // Game-specific attributes of this component (cost, colour, points...)
+    properties: Properties,
 }
 
 impl BaseComponent {
     // Constructor that takes only the type (default name will be the type's string representation)
-    pub fn new(component_type: ComponentType) -> Self {
+    pub fn new(component_type: ComponentType, ids: &mut IdAllocator) -> Self {
         Self {
-            component_id: GLOBAL_ID.fetch_add(1, Ordering::Relaxed),
+            component_id: ids.next_id(),
             component_name: component_type.to_string(),
             component_type,
             owner_id: -1,
+            properties: Properties::new(),
         }
     }
 
     // Constructor that takes the type and name of the component
-    pub fn new_with_name(component_type: ComponentType, name: &str) -> Self {
+    pub fn new_with_name(component_type: ComponentType, name: &str, ids: &mut IdAllocator) -> Self {
         Self {
-            component_id: GLOBAL_ID.fetch_add(1, Ordering::Relaxed),
+            component_id: ids.next_id(),
             component_type,
             owner_id: -1,
             component_name: name.to_string(),
+            properties: Properties::new(),
         }
     }
 
@@ -67,6 +121,7 @@ impl BaseComponent {
             component_type,
             owner_id: -1,
             component_name: name.to_string(),
+            properties: Properties::new(),
         }
     }
 
@@ -77,6 +132,7 @@ impl BaseComponent {
             component_name: component_type.to_string(),
             component_type,
             owner_id: -1,
+            properties: Properties::new(),
         }
     }
 
@@ -99,11 +155,36 @@ impl BaseComponent {
     pub fn set_component_name(&mut self, name: String) {
         self.component_name = name;
     }
+
+    pub fn properties(&self) -> &Properties {
+        &self.properties
+    }
+
+    pub fn properties_mut(&mut self) -> &mut Properties {
+        &mut self.properties
+    }
+
+    pub fn get_property(&self, key: impl Into<PropertyKey>) -> Option<&PropertyValue> {
+        self.properties.get(key)
+    }
+
+    /// Sets a property, returning its previous value (if any)
+    pub fn set_property(&mut self, name: &str, value: PropertyValue) -> Option<PropertyValue> {
+        self.properties.set(name, value)
+    }
+
+    pub fn remove_property(&mut self, key: impl Into<PropertyKey>) -> Option<PropertyValue> {
+        self.properties.remove(key)
+    }
 }
 
 impl Component for BaseComponent {
-    fn component_id(&self) -> usize {
-        self.component_id
+    fn base(&self) -> &BaseComponent {
+        self
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        self
     }
 
     fn nested_components(&self) -> Vec<Box<dyn Component>> {
@@ -114,6 +195,21 @@ impl Component for BaseComponent {
 impl Hash for BaseComponent {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.component_id.hash(state);
+        self.properties.hash(state);
+    }
+}
+
+impl IRenderable for BaseComponent {
+    fn short_string(&self) -> String {
+        self.component_name.clone()
+    }
+
+    fn detailed_string(&self) -> String {
+        if self.properties.is_empty() {
+            self.short_string()
+        } else {
+            format!("{} {}", self.component_name, self.properties)
+        }
     }
 }
 
@@ -125,7 +221,7 @@ impl fmt::Display for BaseComponent {
 
 impl PartialEq for BaseComponent {
     fn eq(&self, other: &Self) -> bool {
-        self.component_id == other.component_id
+        self.component_id == other.component_id && self.properties == other.properties
     }
 }
 
@@ -137,10 +233,33 @@ mod tests {
 
     #[test]
     fn test_different_ids() {
-        let t1 = BaseComponent::new(ComponentType::Token);
+        let mut ids = IdAllocator::new();
+        let t1 = BaseComponent::new(ComponentType::Token, &mut ids);
         assert_eq!(t1.component_id(), 0);
-        let t2 = BaseComponent::new(ComponentType::Token);
+        let t2 = BaseComponent::new(ComponentType::Token, &mut ids);
         assert_eq!(t2.component_id(), 1);
         assert_ne!(t1, t2);
+
+        // A separate allocator, e.g. for another game, is not affected by the first one
+        let mut other_ids = IdAllocator::new();
+        assert_eq!(
+            BaseComponent::new(ComponentType::Card, &mut other_ids).component_id(),
+            0
+        );
+        assert_eq!(ids.peek(), 2);
+    }
+
+    #[test]
+    fn test_properties_in_equality() {
+        let mut c1 = BaseComponent::new_with_id(ComponentType::Token, 10);
+        let c2 = c1.clone();
+        assert_eq!(c1, c2);
+
+        assert_eq!(c1.set_property("points", 2.into()), None);
+        assert_eq!(c1.get_property("points"), Some(&2.into()));
+        assert_ne!(c1, c2);
+
+        assert_eq!(c1.remove_property("points"), Some(2.into()));
+        assert_eq!(c1, c2);
     }
 }
@@ -0,0 +1,716 @@
+//! Builds components from JSON data files, so card lists and boards can be tweaked without
+//! touching Rust code. Each game keeps its files in its own data directory:
+//!
+//! - Decks: `{"name": "Draw pile", "owner": -1, "visibility": "HiddenToAll", "capacity": 40,
+//!   "cards": [{"name": "Fireball", "count": 2, "properties": {"cost": 3, "colour": "Red"}}]}`
+//! - Tokens: `{"tokens": [{"type": "Stone", "count": 10, "owner": 0, "properties": {...}}]}`
+//! - Grid boards: `{"width": 3, "height": 3, "cells": [["X", null, null], ...]}`, where each
+//!   cell is a token type or null
+//! - Graph boards: `{"name": "Map", "nodes": [{"name": "A", "properties": {...}}],
+//!   "edges": [{"from": "A", "to": "B", "directed": false, "weight": 2, "properties": {...}}]}`
+//! - Hex boards: `{"radius": 3}` or `{"width": 5, "height": 4, "layout": "OddR"}`
+//!
+//! Property values can be integers, strings, booleans or lists of those. Unknown fields are
+//! rejected, so typos are reported rather than silently ignored.
+
+use crate::main_code::{
+    core::{
+        components::{
+            card::Card,
+            component::Component,
+            deck::Deck,
+            graph_board::{BoardNode, Edge, GraphBoard},
+            grid_board::GridBoard,
+            hex_board::{HexBoard, OffsetLayout},
+            token::Token,
+        },
+        core_constants::VisibilityMode,
+        id_allocator::IdAllocator,
+        properties::PropertyValue,
+    },
+    utilities::json_utils::{load_json_file, JsonError},
+};
+use serde_json::{Map, Value};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// Problem with the contents of a data file, located by its path inside the JSON document
+/// (e.g. `cards[2].properties.cost`).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SchemaError {
+    pub location: String,
+    pub message: String,
+}
+
+impl SchemaError {
+    fn new(location: &str, message: impl Into<String>) -> Self {
+        Self {
+            location: location.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.location.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.location, self.message)
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Errors produced when loading components from data files.
+#[derive(Debug)]
+pub enum DataError {
+    Json(JsonError),
+    Schema { path: String, error: SchemaError },
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::Json(e) => write!(f, "{}", e),
+            DataError::Schema { path, error } => write!(f, "invalid data in '{}': {}", path, error),
+        }
+    }
+}
+
+impl std::error::Error for DataError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DataError::Json(e) => Some(e),
+            DataError::Schema { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<JsonError> for DataError {
+    fn from(e: JsonError) -> Self {
+        DataError::Json(e)
+    }
+}
+
+/// Loads components from the JSON files of a game's data directory.
+#[derive(Debug, Clone)]
+pub struct ComponentLoader {
+    data_path: PathBuf,
+}
+
+impl ComponentLoader {
+    pub fn new(data_path: impl Into<PathBuf>) -> Self {
+        Self {
+            data_path: data_path.into(),
+        }
+    }
+
+    pub fn data_path(&self) -> &Path {
+        &self.data_path
+    }
+
+    /// Cards listed in a deck file, with their copies expanded
+    pub fn load_cards(&self, file: &str, ids: &mut IdAllocator) -> Result<Vec<Card>, DataError> {
+        self.load(file, |json| cards_from_json(json, ids))
+    }
+
+    pub fn load_deck(&self, file: &str, ids: &mut IdAllocator) -> Result<Deck<Card>, DataError> {
+        self.load(file, |json| deck_from_json(json, ids))
+    }
+
+    pub fn load_tokens(&self, file: &str, ids: &mut IdAllocator) -> Result<Vec<Token>, DataError> {
+        self.load(file, |json| tokens_from_json(json, ids))
+    }
+
+    pub fn load_grid_board(
+        &self,
+        file: &str,
+        ids: &mut IdAllocator,
+    ) -> Result<GridBoard<Token>, DataError> {
+        self.load(file, |json| grid_board_from_json(json, ids))
+    }
+
+    pub fn load_graph_board(
+        &self,
+        file: &str,
+        ids: &mut IdAllocator,
+    ) -> Result<GraphBoard, DataError> {
+        self.load(file, |json| graph_board_from_json(json, ids))
+    }
+
+    pub fn load_hex_board(
+        &self,
+        file: &str,
+        ids: &mut IdAllocator,
+    ) -> Result<HexBoard<Token>, DataError> {
+        self.load(file, |json| hex_board_from_json(json, ids))
+    }
+
+    fn load<T>(
+        &self,
+        file: &str,
+        parse: impl FnOnce(&Value) -> Result<T, SchemaError>,
+    ) -> Result<T, DataError> {
+        let path = self.data_path.join(file);
+        let json = load_json_file(&path)?;
+        parse(&json).map_err(|error| DataError::Schema {
+            path: path.display().to_string(),
+            error,
+        })
+    }
+}
+
+fn child(location: &str, key: &str) -> String {
+    if location.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", location, key)
+    }
+}
+
+/// Checks that the value is an object with no fields other than the allowed ones
+fn object<'a>(
+    value: &'a Value,
+    location: &str,
+    allowed: &[&str],
+) -> Result<&'a Map<String, Value>, SchemaError> {
+    let obj = value
+        .as_object()
+        .ok_or_else(|| SchemaError::new(location, "expected an object"))?;
+    match obj.keys().find(|k| !allowed.contains(&k.as_str())) {
+        Some(key) => Err(SchemaError::new(
+            location,
+            format!(
+                "unknown field '{}' (expected one of: {})",
+                key,
+                allowed.join(", ")
+            ),
+        )),
+        None => Ok(obj),
+    }
+}
+
+fn required<'a>(
+    obj: &'a Map<String, Value>,
+    key: &str,
+    location: &str,
+) -> Result<&'a Value, SchemaError> {
+    obj.get(key)
+        .ok_or_else(|| SchemaError::new(location, format!("missing field '{}'", key)))
+}
+
+fn as_str<'a>(value: &'a Value, location: &str) -> Result<&'a str, SchemaError> {
+    value
+        .as_str()
+        .ok_or_else(|| SchemaError::new(location, format!("expected a string, found {}", value)))
+}
+
+fn as_usize(value: &Value, location: &str) -> Result<usize, SchemaError> {
+    value.as_u64().map(|v| v as usize).ok_or_else(|| {
+        SchemaError::new(
+            location,
+            format!("expected a non-negative integer, found {}", value),
+        )
+    })
+}
+
+fn as_array<'a>(value: &'a Value, location: &str) -> Result<&'a Vec<Value>, SchemaError> {
+    value
+        .as_array()
+        .ok_or_else(|| SchemaError::new(location, format!("expected a list, found {}", value)))
+}
+
+fn optional_usize(
+    obj: &Map<String, Value>,
+    key: &str,
+    location: &str,
+) -> Result<Option<usize>, SchemaError> {
+    obj.get(key)
+        .map(|v| as_usize(v, &child(location, key)))
+        .transpose()
+}
+
+fn owner(obj: &Map<String, Value>, location: &str) -> Result<i32, SchemaError> {
+    match obj.get("owner") {
+        None => Ok(-1),
+        Some(v) => v
+            .as_i64()
+            .filter(|o| *o >= -1 && *o <= i32::MAX as i64)
+            .map(|o| o as i32)
+            .ok_or_else(|| {
+                SchemaError::new(
+                    &child(location, "owner"),
+                    format!("expected a player index or -1, found {}", v),
+                )
+            }),
+    }
+}
+
+/// Number of copies described by an entry, at least 1
+fn count(obj: &Map<String, Value>, location: &str) -> Result<usize, SchemaError> {
+    match optional_usize(obj, "count", location)? {
+        Some(0) => Err(SchemaError::new(
+            &child(location, "count"),
+            "count must be at least 1",
+        )),
+        Some(n) => Ok(n),
+        None => Ok(1),
+    }
+}
+
+fn property_value(value: &Value, location: &str) -> Result<PropertyValue, SchemaError> {
+    match value {
+        Value::Bool(b) => Ok(PropertyValue::Bool(*b)),
+        Value::String(s) => Ok(PropertyValue::Text(s.clone())),
+        Value::Number(n) => n
+            .as_i64()
+            .map(PropertyValue::Int)
+            .ok_or_else(|| SchemaError::new(location, format!("expected an integer, found {}", n))),
+        Value::Array(values) => values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| property_value(v, &format!("{}[{}]", location, i)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(PropertyValue::List),
+        _ => Err(SchemaError::new(
+            location,
+            format!(
+                "expected an integer, string, boolean or list, found {}",
+                value
+            ),
+        )),
+    }
+}
+
+/// Parses the optional "properties" object of an entry, in name order
+fn properties(
+    obj: &Map<String, Value>,
+    location: &str,
+) -> Result<BTreeMap<String, PropertyValue>, SchemaError> {
+    let location = child(location, "properties");
+    match obj.get("properties") {
+        None => Ok(BTreeMap::new()),
+        Some(v) => v
+            .as_object()
+            .ok_or_else(|| SchemaError::new(&location, "expected an object"))?
+            .iter()
+            .map(|(k, v)| Ok((k.clone(), property_value(v, &child(&location, k))?)))
+            .collect(),
+    }
+}
+
+fn visibility_mode(value: &Value, location: &str) -> Result<VisibilityMode, SchemaError> {
+    match as_str(value, location)? {
+        "VisibleToAll" => Ok(VisibilityMode::VisibleToAll),
+        "HiddenToAll" => Ok(VisibilityMode::HiddenToAll),
+        "VisibleToOwner" => Ok(VisibilityMode::VisibleToOwner),
+        "FirstVisibleToAll" => Ok(VisibilityMode::FirstVisibleToAll),
+        "LastVisibleToAll" => Ok(VisibilityMode::LastVisibleToAll),
+        "MixedVisibility" => Ok(VisibilityMode::MixedVisibility),
+        other => Err(SchemaError::new(
+            location,
+            format!("unknown visibility mode '{}'", other),
+        )),
+    }
+}
+
+fn offset_layout(value: &Value, location: &str) -> Result<OffsetLayout, SchemaError> {
+    match as_str(value, location)? {
+        "OddR" => Ok(OffsetLayout::OddR),
+        "EvenR" => Ok(OffsetLayout::EvenR),
+        "OddQ" => Ok(OffsetLayout::OddQ),
+        "EvenQ" => Ok(OffsetLayout::EvenQ),
+        other => Err(SchemaError::new(
+            location,
+            format!("unknown offset layout '{}'", other),
+        )),
+    }
+}
+
+fn card_from_json(
+    value: &Value,
+    location: &str,
+    ids: &mut IdAllocator,
+) -> Result<Vec<Card>, SchemaError> {
+    let obj = object(value, location, &["name", "count", "owner", "properties"])?;
+    let name = as_str(required(obj, "name", location)?, &child(location, "name"))?;
+    let owner = owner(obj, location)?;
+    let properties = properties(obj, location)?;
+    Ok((0..count(obj, location)?)
+        .map(|_| {
+            let mut card = Card::new(name, ids);
+            card.set_owner_id(owner);
+            for (k, v) in &properties {
+                card.set_property(k, v.clone());
+            }
+            card
+        })
+        .collect())
+}
+
+fn cards_from_list(
+    value: &Value,
+    location: &str,
+    ids: &mut IdAllocator,
+) -> Result<Vec<Card>, SchemaError> {
+    let mut cards = vec![];
+    for (i, entry) in as_array(value, location)?.iter().enumerate() {
+        cards.extend(card_from_json(entry, &format!("{}[{}]", location, i), ids)?);
+    }
+    Ok(cards)
+}
+
+const DECK_FIELDS: [&str; 5] = ["name", "owner", "visibility", "capacity", "cards"];
+
+/// Cards of a deck document, with their copies expanded
+pub fn cards_from_json(json: &Value, ids: &mut IdAllocator) -> Result<Vec<Card>, SchemaError> {
+    let obj = object(json, "", &DECK_FIELDS)?;
+    cards_from_list(required(obj, "cards", "")?, "cards", ids)
+}
+
+pub fn deck_from_json(json: &Value, ids: &mut IdAllocator) -> Result<Deck<Card>, SchemaError> {
+    let obj = object(json, "", &DECK_FIELDS)?;
+    let name = as_str(required(obj, "name", "")?, "name")?;
+    let visibility = match obj.get("visibility") {
+        Some(v) => visibility_mode(v, "visibility")?,
+        None => VisibilityMode::VisibleToAll,
+    };
+    let capacity = optional_usize(obj, "capacity", "")?;
+    let cards = cards_from_list(required(obj, "cards", "")?, "cards", ids)?;
+    if let Some(capacity) = capacity.filter(|c| cards.len() > *c) {
+        return Err(SchemaError::new(
+            "cards",
+            format!(
+                "{} cards do not fit in a deck of capacity {}",
+                cards.len(),
+                capacity
+            ),
+        ));
+    }
+
+    let mut deck = Deck::new(name, owner(obj, "")?, visibility, ids);
+    deck.set_capacity(capacity);
+    for card in cards {
+        deck.add_to_bottom(card);
+    }
+    Ok(deck)
+}
+
+pub fn tokens_from_json(json: &Value, ids: &mut IdAllocator) -> Result<Vec<Token>, SchemaError> {
+    let obj = object(json, "", &["tokens"])?;
+    let mut tokens = vec![];
+    for (i, entry) in as_array(required(obj, "tokens", "")?, "tokens")?
+        .iter()
+        .enumerate()
+    {
+        let location = format!("tokens[{}]", i);
+        let entry = object(entry, &location, &["type", "count", "owner", "properties"])?;
+        let token_type = as_str(
+            required(entry, "type", &location)?,
+            &child(&location, "type"),
+        )?;
+        let owner = owner(entry, &location)?;
+        let properties = properties(entry, &location)?;
+        for _ in 0..count(entry, &location)? {
+            let mut token = Token::new(token_type, ids);
+            token.set_owner_id(owner);
+            for (k, v) in &properties {
+                token.set_property(k, v.clone());
+            }
+            tokens.push(token);
+        }
+    }
+    Ok(tokens)
+}
+
+pub fn grid_board_from_json(
+    json: &Value,
+    ids: &mut IdAllocator,
+) -> Result<GridBoard<Token>, SchemaError> {
+    let obj = object(json, "", &["width", "height", "cells"])?;
+    let width = as_usize(required(obj, "width", "")?, "width")?;
+    let height = as_usize(required(obj, "height", "")?, "height")?;
+    let mut board = GridBoard::new(width, height, ids);
+    let Some(cells) = obj.get("cells") else {
+        return Ok(board);
+    };
+
+    let rows = as_array(cells, "cells")?;
+    if rows.len() != height {
+        return Err(SchemaError::new(
+            "cells",
+            format!("expected {} rows, found {}", height, rows.len()),
+        ));
+    }
+    for (y, row) in rows.iter().enumerate() {
+        let location = format!("cells[{}]", y);
+        let row = as_array(row, &location)?;
+        if row.len() != width {
+            return Err(SchemaError::new(
+                &location,
+                format!("expected {} cells, found {}", width, row.len()),
+            ));
+        }
+        for (x, cell) in row.iter().enumerate() {
+            if !cell.is_null() {
+                let token_type = as_str(cell, &format!("{}[{}]", location, x))?;
+                board.set(x, y, Token::new(token_type, ids));
+            }
+        }
+    }
+    Ok(board)
+}
+
+pub fn graph_board_from_json(
+    json: &Value,
+    ids: &mut IdAllocator,
+) -> Result<GraphBoard, SchemaError> {
+    let obj = object(json, "", &["name", "nodes", "edges"])?;
+    let name = as_str(required(obj, "name", "")?, "name")?;
+    let mut board = GraphBoard::new(name, ids);
+
+    // Edges refer to nodes by name, so names must be unique
+    let mut node_ids: BTreeMap<String, usize> = BTreeMap::new();
+    for (i, entry) in as_array(required(obj, "nodes", "")?, "nodes")?
+        .iter()
+        .enumerate()
+    {
+        let location = format!("nodes[{}]", i);
+        let entry = object(entry, &location, &["name", "properties"])?;
+        let node_name = as_str(
+            required(entry, "name", &location)?,
+            &child(&location, "name"),
+        )?;
+        if node_ids.contains_key(node_name) {
+            return Err(SchemaError::new(
+                &child(&location, "name"),
+                format!("duplicate node '{}'", node_name),
+            ));
+        }
+        let mut node = BoardNode::new(node_name, ids);
+        for (k, v) in properties(entry, &location)? {
+            node.set_property(&k, v);
+        }
+        node_ids.insert(node_name.to_string(), board.add_node(node));
+    }
+
+    let edges = match obj.get("edges") {
+        Some(edges) => as_array(edges, "edges")?.as_slice(),
+        None => &[],
+    };
+    for (i, entry) in edges.iter().enumerate() {
+        let location = format!("edges[{}]", i);
+        let entry = object(
+            entry,
+            &location,
+            &["from", "to", "directed", "weight", "properties"],
+        )?;
+        let node = |key: &str| -> Result<usize, SchemaError> {
+            let node_name = as_str(required(entry, key, &location)?, &child(&location, key))?;
+            node_ids.get(node_name).copied().ok_or_else(|| {
+                SchemaError::new(
+                    &child(&location, key),
+                    format!("unknown node '{}'", node_name),
+                )
+            })
+        };
+        let (from, to) = (node("from")?, node("to")?);
+        let directed = match entry.get("directed") {
+            Some(v) => v.as_bool().ok_or_else(|| {
+                SchemaError::new(
+                    &child(&location, "directed"),
+                    format!("expected a boolean, found {}", v),
+                )
+            })?,
+            None => false,
+        };
+        let mut edge = if directed {
+            Edge::new_directed(from, to)
+        } else {
+            Edge::new(from, to)
+        };
+        if let Some(weight) = optional_usize(entry, "weight", &location)? {
+            edge = edge.with_weight(weight as u64);
+        }
+        for (k, v) in properties(entry, &location)? {
+            edge = edge.with_property(&k, v);
+        }
+        if !board.add_edge(edge) {
+            return Err(SchemaError::new(
+                &location,
+                "an edge must connect nodes of the board",
+            ));
+        }
+    }
+    Ok(board)
+}
+
+pub fn hex_board_from_json(
+    json: &Value,
+    ids: &mut IdAllocator,
+) -> Result<HexBoard<Token>, SchemaError> {
+    let obj = object(json, "", &["radius", "width", "height", "layout"])?;
+    if let Some(radius) = optional_usize(obj, "radius", "")? {
+        if obj.len() > 1 {
+            return Err(SchemaError::new(
+                "",
+                "a hexagonal board only takes a radius",
+            ));
+        }
+        return Ok(HexBoard::new_hexagon(radius as i64, ids));
+    }
+    let width = as_usize(required(obj, "width", "")?, "width")?;
+    let height = as_usize(required(obj, "height", "")?, "height")?;
+    let layout = match obj.get("layout") {
+        Some(v) => offset_layout(v, "layout")?,
+        None => OffsetLayout::OddR,
+    };
+    Ok(HexBoard::new_rectangle(
+        width as i64,
+        height as i64,
+        layout,
+        ids,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::interfaces::component_container::IComponentContainer;
+    use serde_json::json;
+    use std::fs;
+
+    #[test]
+    fn test_load_deck_from_file() {
+        let dir = std::env::temp_dir().join(format!("component_loader_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let deck_json = json!({
+            "name": "Spells",
+            "visibility": "HiddenToAll",
+            "cards": [
+                {"name": "Fireball", "count": 2, "properties": {"cost": 3, "colour": "Red"}},
+                {"name": "Shield", "properties": {"targets": [1, 2], "reusable": true}}
+            ]
+        });
+        fs::write(dir.join("spells.json"), deck_json.to_string()).unwrap();
+
+        let loader = ComponentLoader::new(&dir);
+        let mut ids = IdAllocator::new();
+        let deck = loader.load_deck("spells.json", &mut ids).unwrap();
+        assert_eq!(deck.len(), 3);
+        assert_eq!(deck.get_visibility_mode(), VisibilityMode::HiddenToAll);
+        assert_eq!(deck.get(0).unwrap().name(), "Fireball");
+        assert_eq!(deck.get(1).unwrap().properties().get_int("cost"), Some(3));
+        assert_eq!(
+            deck.get(2).unwrap().properties().get_bool("reusable"),
+            Some(true)
+        );
+        assert_ne!(
+            deck.get(0).unwrap().component_id(),
+            deck.get(1).unwrap().component_id()
+        );
+
+        // The same file loaded with a fresh allocator yields the same IDs
+        let again = loader
+            .load_deck("spells.json", &mut IdAllocator::new())
+            .unwrap();
+        assert_eq!(again.component_id(), deck.component_id());
+        assert_eq!(
+            again.get(2).unwrap().component_id(),
+            deck.get(2).unwrap().component_id()
+        );
+
+        assert!(matches!(
+            loader.load_deck("missing.json", &mut ids),
+            Err(DataError::Json(JsonError::Io { .. }))
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        let mut ids = IdAllocator::new();
+        let error = cards_from_json(
+            &json!({
+                "cards": [{"name": "Fireball"}, {"name": "Ice", "properties": {"cost": 1.5}}]
+            }),
+            &mut ids,
+        )
+        .unwrap_err();
+        assert_eq!(error.location, "cards[1].properties.cost");
+        assert_eq!(
+            error.to_string(),
+            "cards[1].properties.cost: expected an integer, found 1.5"
+        );
+
+        let error =
+            cards_from_json(&json!({"cards": [{"nmae": "Fireball"}]}), &mut ids).unwrap_err();
+        assert_eq!(error.location, "cards[0]");
+        assert!(error.message.starts_with("unknown field 'nmae'"));
+
+        let error = deck_from_json(
+            &json!({
+                "name": "Hand", "capacity": 1, "cards": [{"name": "A", "count": 2}]
+            }),
+            &mut ids,
+        )
+        .unwrap_err();
+        assert_eq!(error.location, "cards");
+
+        let error = graph_board_from_json(
+            &json!({
+                "name": "Map", "nodes": [{"name": "A"}], "edges": [{"from": "A", "to": "B"}]
+            }),
+            &mut ids,
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "edges[0].to: unknown node 'B'");
+    }
+
+    #[test]
+    fn test_tokens_and_boards() {
+        let mut ids = IdAllocator::new();
+        let tokens = tokens_from_json(
+            &json!({"tokens": [{"type": "Gold", "count": 3, "owner": 1}]}),
+            &mut ids,
+        )
+        .unwrap();
+        assert_eq!(tokens.len(), 3);
+        assert!(tokens.iter().all(|t| t.token_type() == "Gold"));
+
+        let grid = grid_board_from_json(
+            &json!({
+                "width": 2, "height": 2, "cells": [["X", null], [null, "O"]]
+            }),
+            &mut ids,
+        )
+        .unwrap();
+        assert_eq!(grid.get(0, 0).unwrap().token_type(), "X");
+        assert_eq!(grid.get(1, 1).unwrap().token_type(), "O");
+        assert!(grid.get(1, 0).is_none());
+
+        let graph = graph_board_from_json(&json!({
+            "name": "Map",
+            "nodes": [{"name": "A", "properties": {"terrain": "forest"}}, {"name": "B"}, {"name": "C"}],
+            "edges": [{"from": "A", "to": "B", "weight": 2}, {"from": "B", "to": "C", "directed": true}]
+        }), &mut ids)
+        .unwrap();
+        let nodes = graph.node_ids();
+        assert_eq!(graph.shortest_path(nodes[0], nodes[2]).unwrap().0, 3);
+        assert!(graph.shortest_path(nodes[2], nodes[0]).is_none());
+
+        let hex = hex_board_from_json(&json!({"radius": 2}), &mut ids).unwrap();
+        assert_eq!(hex.n_hexes(), 19);
+        let hex = hex_board_from_json(
+            &json!({"width": 3, "height": 2, "layout": "EvenQ"}),
+            &mut ids,
+        )
+        .unwrap();
+        assert_eq!(hex.n_hexes(), 6);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::main_code::core::{
+    components::component::{BaseComponent, Component},
+    core_constants::ComponentType,
+    id_allocator::IdAllocator,
+    interfaces::renderable::IRenderable,
+};
+use std::fmt;
+
+/// Error produced when creating a counter whose value is outside its bounds (or whose minimum is
+/// greater than its maximum).
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OutOfBoundsError {
+    pub value: i64,
+    pub minimum: i64,
+    pub maximum: i64,
+}
+
+impl fmt::Display for OutOfBoundsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Counter value {} out of bounds [{}, {}]",
+            self.value, self.minimum, self.maximum
+        )
+    }
+}
+
+impl std::error::Error for OutOfBoundsError {}
+
+/// Bounded integer value, e.g. a score track, health, a resource supply or a round marker.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Counter {
+    base: BaseComponent,
+    value: i64,
+    minimum: i64,
+    maximum: i64,
+    // If true, going past one bound continues from the other one instead of saturating
+    wraparound: bool,
+}
+
+impl Counter {
+    pub fn new(
+        name: &str,
+        value: i64,
+        minimum: i64,
+        maximum: i64,
+        ids: &mut IdAllocator,
+    ) -> Result<Self, OutOfBoundsError> {
+        Self::check_bounds(value, minimum, maximum)?;
+        Ok(Self {
+            base: BaseComponent::new_with_name(ComponentType::Counter, name, ids),
+            value,
+            minimum,
+            maximum,
+            wraparound: false,
+        })
+    }
+
+    pub fn new_with_id(
+        name: &str,
+        value: i64,
+        minimum: i64,
+        maximum: i64,
+        id: usize,
+    ) -> Result<Self, OutOfBoundsError> {
+        Self::check_bounds(value, minimum, maximum)?;
+        Ok(Self {
+            base: BaseComponent::new_with_name_and_id(ComponentType::Counter, name, id),
+            value,
+            minimum,
+            maximum,
+            wraparound: false,
+        })
+    }
+
+    fn check_bounds(value: i64, minimum: i64, maximum: i64) -> Result<(), OutOfBoundsError> {
+        if minimum <= value && value <= maximum {
+            Ok(())
+        } else {
+            Err(OutOfBoundsError {
+                value,
+                minimum,
+                maximum,
+            })
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.base.component_name()
+    }
+
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    pub fn minimum(&self) -> i64 {
+        self.minimum
+    }
+
+    pub fn maximum(&self) -> i64 {
+        self.maximum
+    }
+
+    pub fn wraparound(&self) -> bool {
+        self.wraparound
+    }
+
+    pub fn set_wraparound(&mut self, wraparound: bool) {
+        self.wraparound = wraparound;
+    }
+
+    pub fn is_minimum(&self) -> bool {
+        self.value == self.minimum
+    }
+
+    pub fn is_maximum(&self) -> bool {
+        self.value == self.maximum
+    }
+
+    /// Sets the value, bringing it within bounds (by clamping or wrapping around). Returns true if
+    /// the value had to be clamped.
+    pub fn set_value(&mut self, value: i64) -> bool {
+        self.set_wide_value(value.into())
+    }
+
+    /// Adds the amount to the value. Returns true if the result had to be clamped.
+    pub fn increment(&mut self, amount: i64) -> bool {
+        self.set_wide_value(i128::from(self.value) + i128::from(amount))
+    }
+
+    /// Subtracts the amount from the value. Returns true if the result had to be clamped.
+    pub fn decrement(&mut self, amount: i64) -> bool {
+        self.set_wide_value(i128::from(self.value) - i128::from(amount))
+    }
+
+    // Same as set_value, computed in i128 so that neither the value (e.g. after an increment) nor
+    // the range of wide bounds overflows
+    fn set_wide_value(&mut self, value: i128) -> bool {
+        let minimum = i128::from(self.minimum);
+        let maximum = i128::from(self.maximum);
+        if self.wraparound {
+            let range = maximum - minimum + 1;
+            self.value = (minimum + (value - minimum).rem_euclid(range)) as i64;
+            false
+        } else {
+            self.value = value.clamp(minimum, maximum) as i64;
+            i128::from(self.value) != value
+        }
+    }
+
+    pub fn set_to_minimum(&mut self) {
+        self.value = self.minimum;
+    }
+
+    pub fn set_to_maximum(&mut self) {
+        self.value = self.maximum;
+    }
+}
+
+impl Component for Counter {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        vec![]
+    }
+}
+
+impl IRenderable for Counter {
+    fn short_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// Value and bounds, e.g. "Health: 8 [0, 10]"
+    fn detailed_string(&self) -> String {
+        format!("{} [{}, {}]", self, self.minimum, self.maximum)
+    }
+}
+
+impl fmt::Display for Counter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name(), self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::components::area::Area;
+
+    #[test]
+    fn test_saturating_changes() {
+        let mut health = Counter::new_with_id("Health", 8, 0, 10, 1).unwrap();
+        assert!(!health.increment(2));
+        assert!(health.is_maximum());
+        assert!(health.increment(1));
+        assert_eq!(health.value(), 10);
+
+        assert!(!health.decrement(10));
+        assert!(health.decrement(1));
+        assert!(health.is_minimum());
+        assert!(health.increment(i64::MAX));
+        assert!(health.is_maximum());
+    }
+
+    #[test]
+    fn test_wraparound() {
+        let mut round = Counter::new_with_id("Round", 3, 1, 4, 1).unwrap();
+        round.set_wraparound(true);
+        assert!(!round.increment(2));
+        assert_eq!(round.value(), 1);
+        assert!(!round.decrement(1));
+        assert_eq!(round.value(), 4);
+        assert!(!round.increment(9));
+        assert_eq!(round.value(), 1);
+
+        let mut wide = Counter::new_with_id("Wide", 0, i64::MIN, i64::MAX, 2).unwrap();
+        wide.set_wraparound(true);
+        assert!(!wide.set_value(i64::MAX));
+        assert_eq!(wide.value(), i64::MAX);
+        assert!(!wide.set_value(i64::MIN));
+        assert_eq!(wide.value(), i64::MIN);
+
+        let mut dial = Counter::new_with_id("Dial", i64::MAX - 1, 0, i64::MAX, 3).unwrap();
+        dial.set_wraparound(true);
+        assert!(!dial.increment(3));
+        assert_eq!(dial.value(), 1);
+        assert!(!dial.decrement(i64::MAX));
+        assert_eq!(dial.value(), 2);
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        assert_eq!(
+            Counter::new_with_id("Health", 11, 0, 10, 1),
+            Err(OutOfBoundsError {
+                value: 11,
+                minimum: 0,
+                maximum: 10
+            })
+        );
+        let mut ids = IdAllocator::new();
+        assert!(Counter::new("Empty", 0, 1, -1, &mut ids).is_err());
+        assert_eq!(ids.next_id(), 0);
+    }
+
+    #[test]
+    fn test_counter_in_area() {
+        let mut area = Area::new_with_id(-1, 10);
+        area.put_component(Box::new(
+            Counter::new_with_id("Score", 0, 0, 100, 1).unwrap(),
+        ));
+        let counter = area.get_component(1).unwrap().downcast_ref::<Counter>();
+        assert_eq!(counter.unwrap().to_string(), "Score: 0");
+    }
+}
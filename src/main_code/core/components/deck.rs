@@ -0,0 +1,395 @@
+use crate::main_code::core::{
+    components::component::{BaseComponent, Component},
+    core_constants::{ComponentType, VisibilityMode},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    interfaces::{
+        component_container::IComponentContainer,
+        renderable::{self, IRenderable},
+    },
+    ownership, visibility,
+};
+use rand::{seq::SliceRandom, Rng, RngCore};
+use std::hash::{Hash, Hasher};
+
+/// Ordered collection of components. Index 0 is the top of the deck.
+#[derive(Debug, Clone)]
+pub struct Deck<T: Component + Clone> {
+    base: BaseComponent,
+    components: Vec<T>,
+    // Maximum number of components in the deck (None if unbounded)
+    capacity: Option<usize>,
+    visibility_mode: VisibilityMode,
+}
+
+impl<T: Component + Clone> Deck<T> {
+    pub fn new(
+        name: &str,
+        owner: i32,
+        visibility_mode: VisibilityMode,
+        ids: &mut IdAllocator,
+    ) -> Self {
+        let mut base = BaseComponent::new_with_name(ComponentType::Deck, name, ids);
+        base.set_owner_id(owner);
+        Self {
+            base,
+            components: vec![],
+            capacity: None,
+            visibility_mode,
+        }
+    }
+
+    pub fn new_with_id(name: &str, owner: i32, id: usize, visibility_mode: VisibilityMode) -> Self {
+        let mut base = BaseComponent::new_with_name_and_id(ComponentType::Deck, name, id);
+        base.set_owner_id(owner);
+        Self {
+            base,
+            components: vec![],
+            capacity: None,
+            visibility_mode,
+        }
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+
+    pub fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.capacity = capacity;
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.capacity.is_some_and(|c| self.components.len() >= c)
+    }
+
+    pub fn len(&self) -> usize {
+        self.components.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.components.is_empty()
+    }
+
+    pub fn components(&self) -> &Vec<T> {
+        &self.components
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.components.get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.components.get_mut(index)
+    }
+
+    pub fn index_of(&self, component_id: usize) -> Option<usize> {
+        self.components
+            .iter()
+            .position(|c| c.component_id() == component_id)
+    }
+
+    pub fn contains(&self, component_id: usize) -> bool {
+        self.index_of(component_id).is_some()
+    }
+
+    /// Adds a component to the top of the deck. Returns false if the deck is full.
+    pub fn add(&mut self, c: T) -> bool {
+        self.add_at(0, c)
+    }
+
+    /// Adds a component to the bottom of the deck. Returns false if the deck is full.
+    pub fn add_to_bottom(&mut self, c: T) -> bool {
+        self.add_at(self.components.len(), c)
+    }
+
+    /// Inserts a component at the given index. Returns false if the deck is full or the index is
+    /// greater than the size of the deck.
+    pub fn add_at(&mut self, index: usize, c: T) -> bool {
+        if self.is_full() || index > self.components.len() {
+            return false;
+        }
+        self.components.insert(index, c);
+        true
+    }
+
+    /// Adds all the components to the top of the deck, keeping their order (the first one ends on
+    /// top). Returns false, without adding any, if they don't fit.
+    pub fn add_all(&mut self, components: Vec<T>) -> bool {
+        if self
+            .capacity
+            .is_some_and(|c| self.components.len() + components.len() > c)
+        {
+            return false;
+        }
+        self.components.splice(0..0, components);
+        true
+    }
+
+    /// Removes and returns the top component
+    pub fn draw(&mut self) -> Option<T> {
+        if self.components.is_empty() {
+            None
+        } else {
+            Some(self.components.remove(0))
+        }
+    }
+
+    /// Removes and returns the bottom component
+    pub fn draw_from_bottom(&mut self) -> Option<T> {
+        self.components.pop()
+    }
+
+    /// Returns the top component without removing it
+    pub fn peek(&self) -> Option<&T> {
+        self.components.first()
+    }
+
+    /// Returns the first `amount` components from the top, without removing them
+    pub fn peek_n(&self, amount: usize) -> &[T] {
+        &self.components[..amount.min(self.components.len())]
+    }
+
+    /// Removes and returns the component at the given index
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        if index < self.components.len() {
+            Some(self.components.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Removes and returns the component with the given ID
+    pub fn remove_by_id(&mut self, component_id: usize) -> Option<T> {
+        self.index_of(component_id).and_then(|i| self.remove_at(i))
+    }
+
+    /// Shuffles the deck with the given random source (e.g. the game state's RNG)
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rnd: &mut R) {
+        self.components.shuffle(rnd);
+    }
+
+    pub fn clear(&mut self) {
+        self.components.clear()
+    }
+
+    /// Returns a copy of the deck as the player could imagine it: the components they cannot see
+    /// are shuffled among their positions, while the visible ones stay where they are.
+    pub fn copy_for_player<R: Rng + ?Sized>(
+        &self,
+        gs: &dyn GameState,
+        player: usize,
+        rnd: &mut R,
+    ) -> Self {
+        let mut copy = self.clone();
+        let hidden = visibility::hidden_indices(gs, self, player);
+        let mut shuffled = hidden.clone();
+        shuffled.shuffle(rnd);
+        for (to, from) in hidden.iter().zip(&shuffled) {
+            copy.components[*to] = self.components[*from].clone();
+        }
+        copy
+    }
+}
+
+impl<T: Component + Clone> Component for Deck<T> {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn perspective_copy(
+        &self,
+        gs: &dyn GameState,
+        player: usize,
+        rnd: &mut dyn RngCore,
+    ) -> Option<Box<dyn Component>> {
+        Some(Box::new(self.copy_for_player(gs, player, rnd)))
+    }
+
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        self.get_components()
+    }
+}
+
+impl<T: Component + Clone> IComponentContainer for Deck<T> {
+    fn get_components(&self) -> Vec<Box<dyn Component>> {
+        self.components
+            .iter()
+            .map(|c| Box::new(c.clone()) as Box<dyn Component>)
+            .collect()
+    }
+
+    fn get_visibility_mode(&self) -> VisibilityMode {
+        self.visibility_mode.clone()
+    }
+
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode) {
+        self.visibility_mode = visibility_mode;
+    }
+
+    fn set_owner_of(&mut self, component_id: usize, owner_id: i32) -> Option<i32> {
+        ownership::set_owner_among(
+            self.components.iter_mut().map(|c| c as &mut dyn Component),
+            component_id,
+            owner_id,
+        )
+    }
+}
+
+impl<T: Component + Clone> IRenderable for Deck<T> {
+    /// Name and size, e.g. "Hand (5)"
+    fn short_string(&self) -> String {
+        format!("{} ({})", self.base.component_name(), self.components.len())
+    }
+
+    fn detailed_string(&self) -> String {
+        let components: Vec<String> = self
+            .components
+            .iter()
+            .map(|c| c.detailed_string())
+            .collect();
+        format!(
+            "{}: [{}]",
+            self.base.component_name(),
+            components.join(", ")
+        )
+    }
+
+    fn string_for_player(&self, gs: &dyn GameState, player: usize) -> String {
+        format!(
+            "{}: [{}]",
+            self.base.component_name(),
+            renderable::contents_for_player(gs, self, player).join(", ")
+        )
+    }
+}
+
+impl<T: Component + Clone> Hash for Deck<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.component_id().hash(state);
+        for c in &self.components {
+            c.component_id().hash(state);
+        }
+    }
+}
+
+impl<T: Component + Clone> PartialEq for Deck<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.component_id() == other.component_id()
+            && self
+                .components
+                .iter()
+                .map(|c| c.component_id())
+                .eq(other.components.iter().map(|c| c.component_id()))
+    }
+}
+
+impl<T: Component + Clone> Eq for Deck<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        components::token::Token, game_parameters::AbstractParameters,
+        game_state::AbstractGameState,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn ids(deck: &Deck<Token>) -> Vec<usize> {
+        deck.components().iter().map(|c| c.component_id()).collect()
+    }
+
+    fn deck_of(n: usize) -> Deck<Token> {
+        let mut deck = Deck::new_with_id("Deck", -1, 100, VisibilityMode::HiddenToAll);
+        for i in 0..n {
+            deck.add_to_bottom(Token::new_with_id("Card", i));
+        }
+        deck
+    }
+
+    #[test]
+    fn test_add_draw_and_peek() {
+        let mut deck = deck_of(3);
+        assert_eq!(ids(&deck), vec![0, 1, 2]);
+
+        deck.add(Token::new_with_id("Card", 3));
+        assert!(deck.add_at(2, Token::new_with_id("Card", 4)));
+        assert!(!deck.add_at(6, Token::new_with_id("Card", 5)));
+        assert_eq!(ids(&deck), vec![3, 0, 4, 1, 2]);
+        assert_eq!(deck.peek().unwrap().component_id(), 3);
+        assert_eq!(deck.peek_n(10).len(), 5);
+
+        assert_eq!(deck.draw().unwrap().component_id(), 3);
+        assert_eq!(deck.draw_from_bottom().unwrap().component_id(), 2);
+        assert_eq!(deck.remove_by_id(4).unwrap().component_id(), 4);
+        assert!(deck.remove_by_id(4).is_none());
+        assert_eq!(ids(&deck), vec![0, 1]);
+        assert_eq!(deck.get_size(), 2);
+        assert_eq!(deck.get_visibility_mode(), VisibilityMode::HiddenToAll);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut deck = deck_of(2);
+        deck.set_capacity(Some(3));
+        assert!(deck.add(Token::new_with_id("Card", 2)));
+        assert!(deck.is_full());
+        assert!(!deck.add_to_bottom(Token::new_with_id("Card", 3)));
+        assert_eq!(deck.len(), 3);
+
+        deck.draw();
+        assert!(!deck.add_all(vec![
+            Token::new_with_id("Card", 4),
+            Token::new_with_id("Card", 5)
+        ]));
+        assert!(deck.add_all(vec![Token::new_with_id("Card", 4)]));
+    }
+
+    #[test]
+    fn test_seeded_shuffle() {
+        let mut d1 = deck_of(20);
+        let mut d2 = deck_of(20);
+        d1.shuffle(&mut StdRng::seed_from_u64(1));
+        d2.shuffle(&mut StdRng::seed_from_u64(1));
+        assert_eq!(ids(&d1), ids(&d2));
+        assert_ne!(ids(&d1), ids(&deck_of(20)));
+
+        let mut sorted = ids(&d1);
+        sorted.sort();
+        assert_eq!(sorted, ids(&deck_of(20)));
+    }
+
+    #[test]
+    fn test_perspective_copy_and_rendering() {
+        let gs = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let mut deck = deck_of(20);
+        deck.set_visibility_mode(VisibilityMode::FirstVisibleToAll);
+
+        let copy = deck.copy_for_player(&gs, 1, &mut StdRng::seed_from_u64(5));
+        assert_eq!(copy.get(0).unwrap().component_id(), 0);
+        assert_ne!(ids(&copy), ids(&deck));
+
+        let mut hand = deck_of(3);
+        assert_eq!(hand.short_string(), "Deck (3)");
+        assert_eq!(hand.string_for_player(&gs, 0), "Deck: [?, ?, ?]");
+        hand.set_visibility_mode(VisibilityMode::VisibleToAll);
+        assert_eq!(hand.string_for_player(&gs, 0), "Deck: [Card, Card, Card]");
+        hand.set_visibility_mode(VisibilityMode::VisibleToOwner);
+        assert_eq!(hand.string_for_player(&gs, 1), "Deck: [?, ?, ?]");
+        hand.set_visibility_mode(VisibilityMode::LastVisibleToAll);
+        assert_eq!(hand.string_for_player(&gs, 1), "Deck: [?, ?, Card]");
+        let copy = hand.copy_for_player(&gs, 1, &mut StdRng::seed_from_u64(5));
+        assert_eq!(copy.get(2).unwrap().component_id(), 2);
+    }
+}
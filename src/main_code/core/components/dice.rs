@@ -0,0 +1,424 @@
+use crate::main_code::core::{
+    components::component::{BaseComponent, Component},
+    core_constants::{ComponentType, VisibilityMode},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    interfaces::{
+        component_container::IComponentContainer,
+        renderable::{self, IRenderable},
+    },
+    ownership,
+};
+use rand::Rng;
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// Face of a dice: either a numeric value or a symbol.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum DiceFace {
+    Value(i64),
+    Symbol(String),
+}
+
+impl fmt::Display for DiceFace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiceFace::Value(v) => write!(f, "{}", v),
+            DiceFace::Symbol(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Dice {
+    base: BaseComponent,
+    faces: Vec<DiceFace>,
+    // Index of the face currently showing
+    face_index: usize,
+    // Locked dice are not rolled
+    locked: bool,
+}
+
+impl Dice {
+    /// N-sided dice with faces 1 to N
+    pub fn new(n_sides: usize, ids: &mut IdAllocator) -> Self {
+        Self::new_with_faces(&format!("d{}", n_sides), Self::numbered_faces(n_sides), ids)
+    }
+
+    pub fn new_with_id(n_sides: usize, id: usize) -> Self {
+        Self::new_with_faces_and_id(&format!("d{}", n_sides), Self::numbered_faces(n_sides), id)
+    }
+
+    /// Dice with custom faces. Panics if there are no faces.
+    pub fn new_with_faces(name: &str, faces: Vec<DiceFace>, ids: &mut IdAllocator) -> Self {
+        assert!(!faces.is_empty(), "A dice needs at least one face");
+        Self {
+            base: BaseComponent::new_with_name(ComponentType::Dice, name, ids),
+            faces,
+            face_index: 0,
+            locked: false,
+        }
+    }
+
+    pub fn new_with_faces_and_id(name: &str, faces: Vec<DiceFace>, id: usize) -> Self {
+        assert!(!faces.is_empty(), "A dice needs at least one face");
+        Self {
+            base: BaseComponent::new_with_name_and_id(ComponentType::Dice, name, id),
+            faces,
+            face_index: 0,
+            locked: false,
+        }
+    }
+
+    fn numbered_faces(n_sides: usize) -> Vec<DiceFace> {
+        (1..=n_sides as i64).map(DiceFace::Value).collect()
+    }
+
+    pub fn name(&self) -> &str {
+        self.base.component_name()
+    }
+
+    pub fn n_sides(&self) -> usize {
+        self.faces.len()
+    }
+
+    pub fn faces(&self) -> &Vec<DiceFace> {
+        &self.faces
+    }
+
+    pub fn face(&self) -> &DiceFace {
+        &self.faces[self.face_index]
+    }
+
+    pub fn face_index(&self) -> usize {
+        self.face_index
+    }
+
+    /// Turns the dice to the given face. Returns false, leaving it unchanged, if there is no such
+    /// face.
+    pub fn set_face_index(&mut self, face_index: usize) -> bool {
+        if face_index >= self.faces.len() {
+            return false;
+        }
+        self.face_index = face_index;
+        true
+    }
+
+    /// Numeric value showing, or None if the face is a symbol
+    pub fn value(&self) -> Option<i64> {
+        match self.face() {
+            DiceFace::Value(v) => Some(*v),
+            DiceFace::Symbol(_) => None,
+        }
+    }
+
+    /// Symbol showing, or None if the face is a numeric value
+    pub fn symbol(&self) -> Option<&str> {
+        match self.face() {
+            DiceFace::Value(_) => None,
+            DiceFace::Symbol(s) => Some(s),
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn lock(&mut self) {
+        self.locked = true;
+    }
+
+    pub fn unlock(&mut self) {
+        self.locked = false;
+    }
+
+    /// Rolls the dice with the given random source (e.g. the game state's RNG). Locked dice are
+    /// not rolled; returns true if the dice was rolled.
+    pub fn roll<R: Rng + ?Sized>(&mut self, rnd: &mut R) -> bool {
+        if self.locked {
+            return false;
+        }
+        self.face_index = rnd.gen_range(0..self.faces.len());
+        true
+    }
+}
+
+impl Component for Dice {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        vec![]
+    }
+}
+
+impl IRenderable for Dice {
+    fn short_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// Current face and all the faces, e.g. "d6: 3 of [1, 2, 3, 4, 5, 6]"
+    fn detailed_string(&self) -> String {
+        let faces: Vec<String> = self.faces.iter().map(|f| f.to_string()).collect();
+        format!(
+            "{}: {} of [{}]",
+            self.base.component_name(),
+            self,
+            faces.join(", ")
+        )
+    }
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.locked {
+            write!(f, "[{}]", self.face())
+        } else {
+            write!(f, "{}", self.face())
+        }
+    }
+}
+
+/// Group of dice rolled together, e.g. the hand of dice of a player.
+#[derive(Debug, Clone)]
+pub struct DicePool {
+    base: BaseComponent,
+    dice: Vec<Dice>,
+    visibility_mode: VisibilityMode,
+}
+
+impl DicePool {
+    pub fn new(owner: i32, ids: &mut IdAllocator) -> Self {
+        let mut base = BaseComponent::new_with_name(ComponentType::DicePool, "DicePool", ids);
+        base.set_owner_id(owner);
+        Self {
+            base,
+            dice: vec![],
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn new_with_id(owner: i32, id: usize) -> Self {
+        let mut base = BaseComponent::new_with_name_and_id(ComponentType::DicePool, "DicePool", id);
+        base.set_owner_id(owner);
+        Self {
+            base,
+            dice: vec![],
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn add(&mut self, dice: Dice) {
+        self.dice.push(dice);
+    }
+
+    pub fn remove_by_id(&mut self, component_id: usize) -> Option<Dice> {
+        let index = self
+            .dice
+            .iter()
+            .position(|d| d.component_id() == component_id)?;
+        Some(self.dice.remove(index))
+    }
+
+    pub fn dice(&self) -> &Vec<Dice> {
+        &self.dice
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Dice> {
+        self.dice.get(index)
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Dice> {
+        self.dice.get_mut(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.dice.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dice.is_empty()
+    }
+
+    /// Rolls every unlocked dice. Returns the number of dice rolled.
+    pub fn roll_all<R: Rng + ?Sized>(&mut self, rnd: &mut R) -> usize {
+        self.dice
+            .iter_mut()
+            .map(|d| d.roll(rnd))
+            .filter(|r| *r)
+            .count()
+    }
+
+    /// Rerolls the dice at the given indices (unless locked). Returns the number of dice rolled,
+    /// or None, without rolling any, if an index is out of the pool.
+    pub fn reroll<R: Rng + ?Sized>(&mut self, indices: &[usize], rnd: &mut R) -> Option<usize> {
+        if indices.iter().any(|i| *i >= self.dice.len()) {
+            return None;
+        }
+        Some(indices.iter().filter(|i| self.dice[**i].roll(rnd)).count())
+    }
+
+    pub fn unlock_all(&mut self) {
+        for d in self.dice.iter_mut() {
+            d.unlock();
+        }
+    }
+
+    /// Sum of the numeric values showing (symbols count as 0)
+    pub fn total(&self) -> i64 {
+        self.dice.iter().filter_map(|d| d.value()).sum()
+    }
+
+    /// Number of dice showing the given face
+    pub fn count_face(&self, face: &DiceFace) -> usize {
+        self.dice.iter().filter(|d| d.face() == face).count()
+    }
+}
+
+impl Component for DicePool {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        self.get_components()
+    }
+}
+
+impl IComponentContainer for DicePool {
+    fn get_components(&self) -> Vec<Box<dyn Component>> {
+        self.dice
+            .iter()
+            .map(|d| Box::new(d.clone()) as Box<dyn Component>)
+            .collect()
+    }
+
+    fn get_visibility_mode(&self) -> VisibilityMode {
+        self.visibility_mode.clone()
+    }
+
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode) {
+        self.visibility_mode = visibility_mode;
+    }
+
+    fn set_owner_of(&mut self, component_id: usize, owner_id: i32) -> Option<i32> {
+        ownership::set_owner_among(
+            self.dice.iter_mut().map(|d| d as &mut dyn Component),
+            component_id,
+            owner_id,
+        )
+    }
+}
+
+impl IRenderable for DicePool {
+    /// Faces of the dice, e.g. "[3, [5], 1]" (locked dice in brackets)
+    fn short_string(&self) -> String {
+        let dice: Vec<String> = self.dice.iter().map(|d| d.to_string()).collect();
+        format!("[{}]", dice.join(", "))
+    }
+
+    fn string_for_player(&self, gs: &dyn GameState, player: usize) -> String {
+        format!(
+            "[{}]",
+            renderable::contents_for_player(gs, self, player).join(", ")
+        )
+    }
+}
+
+impl Hash for DicePool {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.component_id().hash(state);
+        self.dice.hash(state);
+    }
+}
+
+impl PartialEq for DicePool {
+    fn eq(&self, other: &Self) -> bool {
+        self.component_id() == other.component_id() && self.dice == other.dice
+    }
+}
+
+impl Eq for DicePool {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_roll_and_lock() {
+        let mut rnd = StdRng::seed_from_u64(0);
+        let mut d6 = Dice::new_with_id(6, 1);
+        assert_eq!(d6.n_sides(), 6);
+        for _ in 0..50 {
+            assert!(d6.roll(&mut rnd));
+            assert!((1..=6).contains(&d6.value().unwrap()));
+        }
+
+        let face = d6.face_index();
+        d6.lock();
+        assert!(!d6.roll(&mut rnd));
+        assert_eq!(d6.face_index(), face);
+    }
+
+    #[test]
+    fn test_custom_faces() {
+        let faces = vec![DiceFace::Symbol("Skull".to_string()), DiceFace::Value(2)];
+        let mut dice = Dice::new_with_faces_and_id("Risk", faces, 1);
+        assert_eq!(dice.symbol(), Some("Skull"));
+        assert_eq!(dice.value(), None);
+        assert!(dice.set_face_index(1));
+        assert_eq!(dice.value(), Some(2));
+        assert!(!dice.set_face_index(2));
+        assert_eq!(dice.face_index(), 1);
+        assert_eq!(dice.to_string(), "2");
+    }
+
+    #[test]
+    fn test_dice_pool() {
+        let mut pool = DicePool::new_with_id(0, 10);
+        for i in 0..5 {
+            pool.add(Dice::new_with_id(6, i));
+        }
+        assert_eq!(pool.get_size(), 5);
+        assert_eq!(pool.total(), 5);
+        assert_eq!(pool.component_type(), ComponentType::DicePool);
+
+        let mut p1 = pool.clone();
+        let mut p2 = pool.clone();
+        assert_eq!(p1.roll_all(&mut StdRng::seed_from_u64(4)), 5);
+        p2.roll_all(&mut StdRng::seed_from_u64(4));
+        assert_eq!(p1, p2);
+
+        p1.get_mut(0).unwrap().lock();
+        let locked = p1.get(0).unwrap().face().clone();
+        assert_eq!(p1.reroll(&[0, 1], &mut StdRng::seed_from_u64(5)), Some(1));
+        assert_eq!(p1.reroll(&[1, 9], &mut StdRng::seed_from_u64(5)), None);
+        assert_eq!(p1.get(0).unwrap().face(), &locked);
+        assert_eq!(p1.roll_all(&mut StdRng::seed_from_u64(6)), 4);
+
+        p1.unlock_all();
+        assert_eq!(p1.roll_all(&mut StdRng::seed_from_u64(6)), 5);
+        let count: usize = (1..=6).map(|v| p1.count_face(&DiceFace::Value(v))).sum();
+        assert_eq!(count, 5);
+    }
+}
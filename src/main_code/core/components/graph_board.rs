@@ -0,0 +1,683 @@
+use crate::main_code::core::{
+    components::component::{BaseComponent, Component},
+    core_constants::{ComponentType, VisibilityMode},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    interfaces::{
+        component_container::IComponentContainer,
+        renderable::{self, IRenderable},
+    },
+    ownership,
+    properties::{Properties, PropertyKey, PropertyValue},
+};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap, VecDeque},
+    hash::{Hash, Hasher},
+};
+
+/// Space of a GraphBoard, which can hold components and carry properties.
+#[derive(Debug, Clone)]
+pub struct BoardNode {
+    base: BaseComponent,
+    components: Vec<Box<dyn Component>>,
+    visibility_mode: VisibilityMode,
+}
+
+impl BoardNode {
+    pub fn new(name: &str, ids: &mut IdAllocator) -> Self {
+        Self {
+            base: BaseComponent::new_with_name(ComponentType::BoardNode, name, ids),
+            components: vec![],
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn new_with_id(name: &str, id: usize) -> Self {
+        Self {
+            base: BaseComponent::new_with_name_and_id(ComponentType::BoardNode, name, id),
+            components: vec![],
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.base.component_name()
+    }
+
+    pub fn properties(&self) -> &Properties {
+        self.base.properties()
+    }
+
+    pub fn get_property(&self, key: impl Into<PropertyKey>) -> Option<&PropertyValue> {
+        self.base.get_property(key)
+    }
+
+    pub fn set_property(&mut self, name: &str, value: PropertyValue) -> Option<PropertyValue> {
+        self.base.set_property(name, value)
+    }
+
+    pub fn put_component(&mut self, c: Box<dyn Component>) {
+        self.components.push(c);
+    }
+
+    pub fn remove_component(&mut self, component_id: usize) -> Option<Box<dyn Component>> {
+        let index = self
+            .components
+            .iter()
+            .position(|c| c.component_id() == component_id)?;
+        Some(self.components.remove(index))
+    }
+
+    pub fn clear(&mut self) {
+        self.components.clear();
+    }
+}
+
+impl Component for BoardNode {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        let mut nested = vec![];
+        for c in &self.components {
+            nested.extend(c.nested_components());
+            nested.push(c.clone());
+        }
+        nested
+    }
+}
+
+impl IComponentContainer for BoardNode {
+    fn get_components(&self) -> Vec<Box<dyn Component>> {
+        self.components.clone()
+    }
+
+    fn get_visibility_mode(&self) -> VisibilityMode {
+        self.visibility_mode.clone()
+    }
+
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode) {
+        self.visibility_mode = visibility_mode;
+    }
+
+    fn set_owner_of(&mut self, component_id: usize, owner_id: i32) -> Option<i32> {
+        ownership::set_owner_among(
+            self.components.iter_mut().map(|c| c.as_mut()),
+            component_id,
+            owner_id,
+        )
+    }
+}
+
+impl BoardNode {
+    // Name and properties, followed by the given contents if there are any
+    fn render(&self, contents: Vec<String>) -> String {
+        let mut rendered = self.base.detailed_string();
+        if !contents.is_empty() {
+            rendered.push_str(&format!(": [{}]", contents.join(", ")));
+        }
+        rendered
+    }
+}
+
+impl IRenderable for BoardNode {
+    fn short_string(&self) -> String {
+        self.name().to_string()
+    }
+
+    /// Name, properties and contents, e.g. "Forest {food: 2}: [Meeple]"
+    fn detailed_string(&self) -> String {
+        self.render(
+            self.components
+                .iter()
+                .map(|c| c.detailed_string())
+                .collect(),
+        )
+    }
+
+    fn string_for_player(&self, gs: &dyn GameState, player: usize) -> String {
+        self.render(renderable::contents_for_player(gs, self, player))
+    }
+}
+
+impl Hash for BoardNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.base.hash(state);
+        for c in &self.components {
+            c.component_id().hash(state);
+        }
+    }
+}
+
+impl PartialEq for BoardNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
+            && self
+                .components
+                .iter()
+                .map(|c| c.component_id())
+                .eq(other.components.iter().map(|c| c.component_id()))
+    }
+}
+
+impl Eq for BoardNode {}
+
+/// Connection between two nodes of a GraphBoard, identified by their component IDs.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Edge {
+    from: usize,
+    to: usize,
+    // Undirected edges can be traversed both ways
+    directed: bool,
+    // Cost of traversing the edge, used for weighted shortest paths
+    weight: u64,
+    properties: Properties,
+}
+
+impl Edge {
+    pub fn new(from: usize, to: usize) -> Self {
+        Self {
+            from,
+            to,
+            directed: false,
+            weight: 1,
+            properties: Properties::new(),
+        }
+    }
+
+    pub fn new_directed(from: usize, to: usize) -> Self {
+        Self {
+            directed: true,
+            ..Self::new(from, to)
+        }
+    }
+
+    pub fn with_weight(mut self, weight: u64) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub fn with_property(mut self, key: &str, value: PropertyValue) -> Self {
+        self.properties.set(key, value);
+        self
+    }
+
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn weight(&self) -> u64 {
+        self.weight
+    }
+
+    pub fn properties(&self) -> &Properties {
+        &self.properties
+    }
+
+    /// Returns the node reached when traversing the edge from the given node, if possible
+    pub fn traverse_from(&self, node: usize) -> Option<usize> {
+        if self.from == node {
+            Some(self.to)
+        } else if !self.directed && self.to == node {
+            Some(self.from)
+        } else {
+            None
+        }
+    }
+}
+
+/// Board made of an arbitrary graph of spaces (e.g. a map), with nodes keyed by component ID.
+#[derive(Debug, Clone)]
+pub struct GraphBoard {
+    base: BaseComponent,
+    nodes: BTreeMap<usize, BoardNode>,
+    edges: Vec<Edge>,
+    visibility_mode: VisibilityMode,
+}
+
+impl GraphBoard {
+    pub fn new(name: &str, ids: &mut IdAllocator) -> Self {
+        Self {
+            base: BaseComponent::new_with_name(ComponentType::Board, name, ids),
+            nodes: BTreeMap::new(),
+            edges: vec![],
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn new_with_id(name: &str, id: usize) -> Self {
+        Self {
+            base: BaseComponent::new_with_name_and_id(ComponentType::Board, name, id),
+            nodes: BTreeMap::new(),
+            edges: vec![],
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    /// Adds a node, returning its ID
+    pub fn add_node(&mut self, node: BoardNode) -> usize {
+        let id = node.component_id();
+        self.nodes.insert(id, node);
+        id
+    }
+
+    /// Removes a node and every edge touching it
+    pub fn remove_node(&mut self, id: usize) -> Option<BoardNode> {
+        self.edges.retain(|e| e.from != id && e.to != id);
+        self.nodes.remove(&id)
+    }
+
+    pub fn get_node(&self, id: usize) -> Option<&BoardNode> {
+        self.nodes.get(&id)
+    }
+
+    pub fn get_node_mut(&mut self, id: usize) -> Option<&mut BoardNode> {
+        self.nodes.get_mut(&id)
+    }
+
+    /// IDs of every node, in ascending order
+    pub fn node_ids(&self) -> Vec<usize> {
+        self.nodes.keys().copied().collect()
+    }
+
+    pub fn edges(&self) -> &Vec<Edge> {
+        &self.edges
+    }
+
+    /// Adds an edge. Returns false, adding nothing, if either end is not a node of the board.
+    pub fn add_edge(&mut self, edge: Edge) -> bool {
+        if !self.nodes.contains_key(&edge.from) || !self.nodes.contains_key(&edge.to) {
+            return false;
+        }
+        self.edges.push(edge);
+        true
+    }
+
+    /// Adds an undirected edge of weight 1 between two nodes (see add_edge)
+    pub fn connect(&mut self, a: usize, b: usize) -> bool {
+        self.add_edge(Edge::new(a, b))
+    }
+
+    /// Edges that can be traversed from the node
+    pub fn edges_from(&self, id: usize) -> Vec<&Edge> {
+        self.edges
+            .iter()
+            .filter(|e| e.traverse_from(id).is_some())
+            .collect()
+    }
+
+    /// Nodes reachable from the node through a single edge, in ascending order
+    pub fn neighbours(&self, id: usize) -> Vec<usize> {
+        let neighbours: BTreeSet<usize> = self
+            .edges
+            .iter()
+            .filter_map(|e| e.traverse_from(id))
+            .collect();
+        neighbours.into_iter().collect()
+    }
+
+    /// Path with the fewest edges between two nodes (both included), ignoring weights
+    pub fn shortest_path_bfs(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        if !self.nodes.contains_key(&from) || !self.nodes.contains_key(&to) {
+            return None;
+        }
+        let mut previous: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut visited = BTreeSet::from([from]);
+        let mut queue = VecDeque::from([from]);
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                return Some(Self::build_path(&previous, from, to));
+            }
+            for next in self.neighbours(node) {
+                if visited.insert(next) {
+                    previous.insert(next, node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Path with the lowest total weight between two nodes (both included), and its cost. Costs
+    /// too large for a u64 saturate at u64::MAX.
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<(u64, Vec<usize>)> {
+        if !self.nodes.contains_key(&from) || !self.nodes.contains_key(&to) {
+            return None;
+        }
+        let mut distances: BTreeMap<usize, u64> = BTreeMap::from([(from, 0)]);
+        let mut previous: BTreeMap<usize, usize> = BTreeMap::new();
+        let mut heap = BinaryHeap::from([Reverse((0, from))]);
+        while let Some(Reverse((cost, node))) = heap.pop() {
+            if node == to {
+                return Some((cost, Self::build_path(&previous, from, to)));
+            }
+            if cost > distances[&node] {
+                continue;
+            }
+            for edge in self.edges_from(node) {
+                let next = edge.traverse_from(node).unwrap();
+                let next_cost = cost.saturating_add(edge.weight);
+                if distances.get(&next).is_none_or(|d| next_cost < *d) {
+                    distances.insert(next, next_cost);
+                    previous.insert(next, node);
+                    heap.push(Reverse((next_cost, next)));
+                }
+            }
+        }
+        None
+    }
+
+    fn build_path(previous: &BTreeMap<usize, usize>, from: usize, to: usize) -> Vec<usize> {
+        let mut path = vec![to];
+        let mut node = to;
+        while node != from {
+            node = previous[&node];
+            path.push(node);
+        }
+        path.reverse();
+        path
+    }
+
+    /// Nodes reachable from the node in at most `steps` edges (including the node itself)
+    pub fn reachable_within(&self, from: usize, steps: usize) -> BTreeSet<usize> {
+        let mut reached = BTreeSet::from([from]);
+        let mut frontier = vec![from];
+        for _ in 0..steps {
+            let mut next_frontier = vec![];
+            for node in frontier {
+                for next in self.neighbours(node) {
+                    if reached.insert(next) {
+                        next_frontier.push(next);
+                    }
+                }
+            }
+            frontier = next_frontier;
+        }
+        reached
+    }
+
+    /// Groups of nodes connected to each other, ignoring edge direction. Each group is sorted,
+    /// and groups are ordered by their smallest node ID.
+    pub fn connected_components(&self) -> Vec<Vec<usize>> {
+        let mut visited = BTreeSet::new();
+        let mut groups = vec![];
+        for &start in self.nodes.keys() {
+            if !visited.insert(start) {
+                continue;
+            }
+            let mut group = vec![start];
+            let mut stack = vec![start];
+            while let Some(node) = stack.pop() {
+                for edge in &self.edges {
+                    let next = if edge.from == node {
+                        edge.to
+                    } else if edge.to == node {
+                        edge.from
+                    } else {
+                        continue;
+                    };
+                    if visited.insert(next) {
+                        group.push(next);
+                        stack.push(next);
+                    }
+                }
+            }
+            group.sort();
+            groups.push(group);
+        }
+        groups
+    }
+}
+
+impl Component for GraphBoard {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        let mut nested = vec![];
+        for node in self.nodes.values() {
+            nested.extend(node.nested_components());
+            nested.push(Box::new(node.clone()) as Box<dyn Component>);
+        }
+        nested
+    }
+}
+
+impl IComponentContainer for GraphBoard {
+    fn get_components(&self) -> Vec<Box<dyn Component>> {
+        self.nodes
+            .values()
+            .map(|n| Box::new(n.clone()) as Box<dyn Component>)
+            .collect()
+    }
+
+    fn get_visibility_mode(&self) -> VisibilityMode {
+        self.visibility_mode.clone()
+    }
+
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode) {
+        self.visibility_mode = visibility_mode;
+    }
+
+    fn set_owner_of(&mut self, component_id: usize, owner_id: i32) -> Option<i32> {
+        ownership::set_owner_among(
+            self.nodes.values_mut().map(|n| n as &mut dyn Component),
+            component_id,
+            owner_id,
+        )
+    }
+}
+
+impl GraphBoard {
+    // One line per node, followed by one line per edge ("a -> b" if directed, "a - b" otherwise)
+    fn render(&self, nodes: Vec<String>) -> String {
+        let mut rendered = String::new();
+        for node in nodes {
+            rendered.push_str(&node);
+            rendered.push('\n');
+        }
+        for edge in &self.edges {
+            let name = |id: usize| self.nodes.get(&id).map_or("?", |n| n.name());
+            let arrow = if edge.directed { "->" } else { "-" };
+            rendered.push_str(&format!(
+                "{} {} {}\n",
+                name(edge.from),
+                arrow,
+                name(edge.to)
+            ));
+        }
+        rendered
+    }
+}
+
+impl IRenderable for GraphBoard {
+    /// Name and size, e.g. "Map (12 nodes, 20 edges)"
+    fn short_string(&self) -> String {
+        format!(
+            "{} ({} nodes, {} edges)",
+            self.base.component_name(),
+            self.nodes.len(),
+            self.edges.len()
+        )
+    }
+
+    fn detailed_string(&self) -> String {
+        self.render(self.nodes.values().map(|n| n.detailed_string()).collect())
+    }
+
+    fn string_for_player(&self, gs: &dyn GameState, player: usize) -> String {
+        self.render(renderable::contents_for_player(gs, self, player))
+    }
+}
+
+impl Hash for GraphBoard {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.component_id().hash(state);
+        for node in self.nodes.values() {
+            node.hash(state);
+        }
+        self.edges.hash(state);
+    }
+}
+
+impl PartialEq for GraphBoard {
+    fn eq(&self, other: &Self) -> bool {
+        self.component_id() == other.component_id()
+            && self.nodes == other.nodes
+            && self.edges == other.edges
+    }
+}
+
+impl Eq for GraphBoard {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::components::token::Token;
+
+    //  1 - 2 - 3 -> 4     6 - 7
+    //   \______5___/
+    fn board() -> GraphBoard {
+        let mut board = GraphBoard::new_with_id("Map", 100);
+        for id in 1..=7 {
+            board.add_node(BoardNode::new_with_id(&format!("Node{}", id), id));
+        }
+        board.connect(1, 2);
+        board.connect(2, 3);
+        board.add_edge(Edge::new_directed(3, 4));
+        board.add_edge(Edge::new(1, 5).with_weight(1));
+        board.add_edge(
+            Edge::new(5, 4)
+                .with_weight(5)
+                .with_property("road", true.into()),
+        );
+        board.connect(6, 7);
+        board
+    }
+
+    #[test]
+    fn test_edges_need_nodes_and_heavy_paths_saturate() {
+        let mut board = board();
+        assert!(!board.connect(1, 99));
+        assert!(!board.add_edge(Edge::new_directed(99, 1)));
+        assert_eq!(board.edges().len(), 6);
+
+        board.add_node(BoardNode::new_with_id("Far", 8));
+        board.add_node(BoardNode::new_with_id("Farther", 9));
+        assert!(board.add_edge(Edge::new(7, 8).with_weight(u64::MAX)));
+        assert!(board.add_edge(Edge::new(8, 9).with_weight(u64::MAX)));
+        assert_eq!(
+            board.shortest_path(6, 9),
+            Some((u64::MAX, vec![6, 7, 8, 9]))
+        );
+    }
+
+    #[test]
+    fn test_neighbours_and_directed_edges() {
+        let board = board();
+        assert_eq!(board.neighbours(3), vec![2, 4]);
+        assert_eq!(board.neighbours(4), vec![5]);
+        assert_eq!(
+            board.edges_from(4)[0].properties().get_bool("road"),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_shortest_paths() {
+        let board = board();
+        assert_eq!(board.shortest_path_bfs(1, 4), Some(vec![1, 5, 4]));
+        assert_eq!(board.shortest_path(1, 4), Some((3, vec![1, 2, 3, 4])));
+        assert_eq!(board.shortest_path(4, 3), Some((8, vec![4, 5, 1, 2, 3])));
+        assert_eq!(board.shortest_path_bfs(1, 7), None);
+        assert_eq!(board.shortest_path(1, 1), Some((0, vec![1])));
+    }
+
+    #[test]
+    fn test_reachability_and_connected_components() {
+        let mut board = board();
+        assert_eq!(board.reachable_within(1, 1), BTreeSet::from([1, 2, 5]));
+        assert_eq!(board.reachable_within(1, 2).len(), 5);
+        assert_eq!(
+            board.connected_components(),
+            vec![vec![1, 2, 3, 4, 5], vec![6, 7]]
+        );
+
+        board.remove_node(1);
+        assert_eq!(
+            board.connected_components(),
+            vec![vec![2, 3, 4, 5], vec![6, 7]]
+        );
+        assert!(board.edges().iter().all(|e| e.from() != 1 && e.to() != 1));
+    }
+
+    #[test]
+    fn test_nodes_hold_components() {
+        let mut board = board();
+        board
+            .get_node_mut(2)
+            .unwrap()
+            .put_component(Box::new(Token::new_with_id("Meeple", 50)));
+        assert_eq!(board.get_size(), 7);
+        assert_eq!(board.nested_components().len(), 8);
+        assert_eq!(board.get_node(2).unwrap().get_size(), 1);
+        assert!(board
+            .get_node_mut(2)
+            .unwrap()
+            .remove_component(50)
+            .is_some());
+    }
+
+    #[test]
+    fn test_rendering() {
+        let mut board = GraphBoard::new_with_id("Route", 100);
+        board.add_node(BoardNode::new_with_id("Start", 1));
+        board.add_node(BoardNode::new_with_id("End", 2));
+        board.add_edge(Edge::new_directed(1, 2));
+        board
+            .get_node_mut(2)
+            .unwrap()
+            .put_component(Box::new(Token::new_with_id("Meeple", 50)));
+
+        assert_eq!(board.short_string(), "Route (2 nodes, 1 edges)");
+        assert_eq!(
+            board.detailed_string(),
+            "Start\nEnd: [Meeple]\nStart -> End\n"
+        );
+    }
+}
@@ -0,0 +1,489 @@
+use crate::main_code::core::{
+    components::component::{BaseComponent, Component},
+    core_constants::{ComponentType, VisibilityMode},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    interfaces::{component_container::IComponentContainer, renderable::IRenderable},
+    ownership, visibility,
+};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// Position of a cell in a grid, as (x, y): x is the column and y the row.
+pub type GridPosition = (usize, usize);
+
+/// Which cells count as neighbours of a cell.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Neighbourhood {
+    /// Up, down, left and right
+    Orthogonal,
+    /// The four diagonal cells
+    Diagonal,
+    /// All eight surrounding cells
+    All,
+}
+
+impl Neighbourhood {
+    fn offsets(&self) -> &'static [(i64, i64)] {
+        match self {
+            Neighbourhood::Orthogonal => &[(0, -1), (1, 0), (0, 1), (-1, 0)],
+            Neighbourhood::Diagonal => &[(1, -1), (1, 1), (-1, 1), (-1, -1)],
+            Neighbourhood::All => &[
+                (0, -1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+                (0, 1),
+                (-1, 1),
+                (-1, 0),
+                (-1, -1),
+            ],
+        }
+    }
+}
+
+/// Rectangular grid of cells, each of which may hold a component.
+#[derive(Debug, Clone)]
+pub struct GridBoard<T: Component + Clone> {
+    base: BaseComponent,
+    width: usize,
+    height: usize,
+    // Cells stored row by row
+    cells: Vec<Option<T>>,
+    visibility_mode: VisibilityMode,
+}
+
+impl<T: Component + Clone> GridBoard<T> {
+    pub fn new(width: usize, height: usize, ids: &mut IdAllocator) -> Self {
+        Self {
+            base: BaseComponent::new_with_name(ComponentType::Board, "GridBoard", ids),
+            width,
+            height,
+            cells: vec![None; width * height],
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn new_with_id(width: usize, height: usize, id: usize) -> Self {
+        Self {
+            base: BaseComponent::new_with_name_and_id(ComponentType::Board, "GridBoard", id),
+            width,
+            height,
+            cells: vec![None; width * height],
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, x: i64, y: i64) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    fn index(&self, x: usize, y: usize) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the component in the cell, or None if the cell is empty or out of bounds
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index(x, y).and_then(|i| self.cells[i].as_ref())
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index(x, y).and_then(|i| self.cells[i].as_mut())
+    }
+
+    /// Puts a component in the cell. Returns false if the cell is out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> bool {
+        match self.index(x, y) {
+            Some(i) => {
+                self.cells[i] = Some(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Empties the cell, returning what it held
+    pub fn clear_cell(&mut self, x: usize, y: usize) -> Option<T> {
+        self.index(x, y).and_then(|i| self.cells[i].take())
+    }
+
+    pub fn is_empty_cell(&self, x: usize, y: usize) -> bool {
+        self.index(x, y).is_some() && self.get(x, y).is_none()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells = vec![None; self.width * self.height];
+    }
+
+    /// Positions of every empty cell, row by row
+    pub fn empty_cells(&self) -> Vec<GridPosition> {
+        self.positions()
+            .filter(|(x, y)| self.get(*x, *y).is_none())
+            .collect()
+    }
+
+    /// Iterates over every position of the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = GridPosition> {
+        let width = self.width;
+        (0..self.width * self.height).map(move |i| (i % width, i / width))
+    }
+
+    /// Positions of the neighbours of a cell that lie within the grid
+    pub fn neighbours(
+        &self,
+        x: usize,
+        y: usize,
+        neighbourhood: Neighbourhood,
+    ) -> Vec<GridPosition> {
+        neighbourhood
+            .offsets()
+            .iter()
+            .map(|(dx, dy)| (x as i64 + dx, y as i64 + dy))
+            .filter(|(nx, ny)| self.in_bounds(*nx, *ny))
+            .map(|(nx, ny)| (nx as usize, ny as usize))
+            .collect()
+    }
+
+    /// Positions from the start cell (included) stepping in the given direction until leaving the
+    /// grid. Empty for the (0, 0) direction, which never leaves it.
+    pub fn scan(&self, start: GridPosition, direction: (i64, i64)) -> Vec<GridPosition> {
+        if direction == (0, 0) {
+            return vec![];
+        }
+        let (mut x, mut y) = (start.0 as i64, start.1 as i64);
+        let mut line = vec![];
+        while self.in_bounds(x, y) {
+            line.push((x as usize, y as usize));
+            x += direction.0;
+            y += direction.1;
+        }
+        line
+    }
+
+    pub fn row(&self, y: usize) -> Vec<Option<&T>> {
+        self.scan((0, y), (1, 0))
+            .into_iter()
+            .map(|(x, y)| self.get(x, y))
+            .collect()
+    }
+
+    pub fn column(&self, x: usize) -> Vec<Option<&T>> {
+        self.scan((x, 0), (0, 1))
+            .into_iter()
+            .map(|(x, y)| self.get(x, y))
+            .collect()
+    }
+
+    /// Every line of the grid: rows, columns, down-right diagonals and down-left diagonals
+    pub fn lines(&self) -> Vec<Vec<GridPosition>> {
+        if self.width == 0 || self.height == 0 {
+            return vec![];
+        }
+        let mut lines = vec![];
+        for y in 0..self.height {
+            lines.push(self.scan((0, y), (1, 0)));
+        }
+        for x in 0..self.width {
+            lines.push(self.scan((x, 0), (0, 1)));
+        }
+        // Down-right diagonals start on the top row or the left column
+        for x in 0..self.width {
+            lines.push(self.scan((x, 0), (1, 1)));
+        }
+        for y in 1..self.height {
+            lines.push(self.scan((0, y), (1, 1)));
+        }
+        // Down-left diagonals start on the top row or the right column
+        for x in 0..self.width {
+            lines.push(self.scan((x, 0), (-1, 1)));
+        }
+        for y in 1..self.height {
+            lines.push(self.scan((self.width - 1, y), (-1, 1)));
+        }
+        lines
+    }
+
+    /// Finds every run of at least `n` consecutive cells (horizontally, vertically or diagonally)
+    /// whose components have the same key. Each run is returned whole, in scan order, and only
+    /// once: with `n` equal to 1, a lone cell is not repeated for each line through it. Nothing is
+    /// found if `n` is 0.
+    pub fn find_runs_by<K: PartialEq>(
+        &self,
+        n: usize,
+        key: impl Fn(&T) -> K,
+    ) -> Vec<Vec<GridPosition>> {
+        let mut runs = vec![];
+        if n == 0 {
+            return runs;
+        }
+        for line in self.lines() {
+            let mut run: Vec<GridPosition> = vec![];
+            let mut run_key: Option<K> = None;
+            for (x, y) in line {
+                let cell_key = self.get(x, y).map(&key);
+                if cell_key.is_some() && cell_key == run_key {
+                    run.push((x, y));
+                    continue;
+                }
+                if run.len() >= n && !runs.contains(&run) {
+                    runs.push(run);
+                }
+                run = if cell_key.is_some() {
+                    vec![(x, y)]
+                } else {
+                    vec![]
+                };
+                run_key = cell_key;
+            }
+            if run.len() >= n && !runs.contains(&run) {
+                runs.push(run);
+            }
+        }
+        runs
+    }
+
+    /// Finds every run of at least `n` consecutive components of the same type and name, e.g.
+    /// tokens of the same player (see find_runs_by). Component IDs are ignored, as every piece
+    /// has its own.
+    pub fn find_runs(&self, n: usize) -> Vec<Vec<GridPosition>> {
+        self.find_runs_by(n, |c| {
+            (c.component_type(), c.base().component_name().to_string())
+        })
+    }
+}
+
+impl<T: Component + Clone> Component for GridBoard<T> {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        self.get_components()
+    }
+}
+
+impl<T: Component + Clone> IComponentContainer for GridBoard<T> {
+    fn get_components(&self) -> Vec<Box<dyn Component>> {
+        self.cells
+            .iter()
+            .flatten()
+            .map(|c| Box::new(c.clone()) as Box<dyn Component>)
+            .collect()
+    }
+
+    fn get_visibility_mode(&self) -> VisibilityMode {
+        self.visibility_mode.clone()
+    }
+
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode) {
+        self.visibility_mode = visibility_mode;
+    }
+
+    fn set_owner_of(&mut self, component_id: usize, owner_id: i32) -> Option<i32> {
+        ownership::set_owner_among(
+            self.cells
+                .iter_mut()
+                .flatten()
+                .map(|c| c as &mut dyn Component),
+            component_id,
+            owner_id,
+        )
+    }
+}
+
+impl<T: Component + Clone> Hash for GridBoard<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.component_id().hash(state);
+        for cell in &self.cells {
+            cell.as_ref().map(|c| c.component_id()).hash(state);
+        }
+    }
+}
+
+impl<T: Component + Clone> PartialEq for GridBoard<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.component_id() == other.component_id()
+            && self.width == other.width
+            && self
+                .cells
+                .iter()
+                .map(|c| c.as_ref().map(|c| c.component_id()))
+                .eq(other
+                    .cells
+                    .iter()
+                    .map(|c| c.as_ref().map(|c| c.component_id())))
+    }
+}
+
+impl<T: Component + Clone> Eq for GridBoard<T> {}
+
+impl<T: Component + Clone> GridBoard<T> {
+    // One line per row, with empty cells as '.' and the cells not shown as '?'
+    fn render(&self, show: impl Fn(&T) -> bool) -> String {
+        let mut rendered = String::new();
+        for y in 0..self.height {
+            let row: Vec<String> = self
+                .row(y)
+                .iter()
+                .map(|c| match c {
+                    None => ".".to_string(),
+                    Some(c) if show(c) => c.short_string(),
+                    Some(_) => "?".to_string(),
+                })
+                .collect();
+            rendered.push_str(&row.join(" "));
+            rendered.push('\n');
+        }
+        rendered
+    }
+}
+
+impl<T: Component + Clone> IRenderable for GridBoard<T> {
+    /// Name and dimensions, e.g. "GridBoard 3x3"
+    fn short_string(&self) -> String {
+        format!(
+            "{} {}x{}",
+            self.base.component_name(),
+            self.width,
+            self.height
+        )
+    }
+
+    fn detailed_string(&self) -> String {
+        self.render(|_| true)
+    }
+
+    fn string_for_player(&self, gs: &dyn GameState, player: usize) -> String {
+        let hidden = visibility::hidden_indices(gs, self, player);
+        if hidden.is_empty() {
+            return self.render(|_| true);
+        }
+        let components = self.get_components();
+        let hidden: Vec<usize> = hidden
+            .iter()
+            .map(|i| components[*i].component_id())
+            .collect();
+        self.render(|c| !hidden.contains(&c.component_id()))
+    }
+}
+
+impl<T: Component + Clone> fmt::Display for GridBoard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.render(|_| true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        components::token::Token, game_parameters::AbstractParameters,
+        game_state::AbstractGameState,
+    };
+
+    fn board(rows: &[&str]) -> GridBoard<Token> {
+        let mut board = GridBoard::new_with_id(rows[0].len(), rows.len(), 100);
+        let mut id = 0;
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if c != '.' {
+                    board.set(x, y, Token::new_with_id(&c.to_string(), id));
+                    id += 1;
+                }
+            }
+        }
+        board
+    }
+
+    #[test]
+    fn test_bounds_and_neighbours() {
+        let mut b = board(&["...", "...", "..."]);
+        assert!(b.set(2, 2, Token::new_with_id("X", 1)));
+        assert!(!b.set(3, 0, Token::new_with_id("X", 2)));
+        assert!(b.get(5, 5).is_none());
+        assert_eq!(b.get_size(), 1);
+        assert_eq!(b.empty_cells().len(), 8);
+
+        assert_eq!(b.neighbours(0, 0, Neighbourhood::Orthogonal).len(), 2);
+        assert_eq!(b.neighbours(1, 1, Neighbourhood::Orthogonal).len(), 4);
+        assert_eq!(
+            b.neighbours(0, 1, Neighbourhood::Diagonal),
+            vec![(1, 0), (1, 2)]
+        );
+        assert_eq!(b.neighbours(1, 1, Neighbourhood::All).len(), 8);
+
+        assert_eq!(b.clear_cell(2, 2).unwrap().component_id(), 1);
+        assert!(b.is_empty_cell(2, 2));
+    }
+
+    #[test]
+    fn test_scans() {
+        let b = board(&["XO.", ".X.", "O.X"]);
+        assert_eq!(b.row(0).iter().filter(|c| c.is_some()).count(), 2);
+        assert_eq!(b.column(0).iter().filter(|c| c.is_some()).count(), 2);
+        assert_eq!(b.scan((0, 0), (1, 1)), vec![(0, 0), (1, 1), (2, 2)]);
+        assert!(b.scan((1, 1), (0, 0)).is_empty());
+        // 3 rows, 3 columns and 5 diagonals in each direction
+        assert_eq!(b.lines().len(), 16);
+        assert_eq!(b.to_string(), "X O .\n. X .\nO . X\n");
+    }
+
+    #[test]
+    fn test_rendering_for_player() {
+        let gs = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let mut b = board(&["XO.", ".X.", "O.X"]);
+        assert_eq!(b.short_string(), "GridBoard 3x3");
+        assert_eq!(b.string_for_player(&gs, 0), b.to_string());
+
+        b.set_visibility_mode(VisibilityMode::HiddenToAll);
+        assert_eq!(b.string_for_player(&gs, 0), "? ? .\n. ? .\n? . ?\n");
+        b.set_visibility_mode(VisibilityMode::FirstVisibleToAll);
+        assert_eq!(b.string_for_player(&gs, 1), "X ? .\n. ? .\n? . ?\n");
+    }
+
+    #[test]
+    fn test_find_runs() {
+        let b = board(&["XXXX.", "OO.O.", "XOX..", "OX.O.", "..X.X"]);
+        let runs = b.find_runs_by(3, |t| t.token_type().to_string());
+        assert_eq!(
+            runs,
+            vec![
+                vec![(0, 0), (1, 0), (2, 0), (3, 0)],
+                vec![(0, 2), (1, 3), (2, 4)]
+            ]
+        );
+
+        assert_eq!(b.find_runs_by(4, |t| t.token_type().to_string()).len(), 1);
+        assert!(b.find_runs_by(0, |t| t.token_type().to_string()).is_empty());
+        assert_eq!(b.find_runs(3), runs);
+
+        let b = board(&["X.", ".O"]);
+        assert_eq!(b.find_runs(1), vec![vec![(0, 0)], vec![(1, 1)]]);
+    }
+}
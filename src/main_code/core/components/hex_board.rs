@@ -0,0 +1,507 @@
+use crate::main_code::core::{
+    components::component::{BaseComponent, Component},
+    core_constants::{ComponentType, VisibilityMode},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    interfaces::{component_container::IComponentContainer, renderable::IRenderable},
+    ownership, visibility,
+};
+use std::{
+    collections::BTreeMap,
+    hash::{Hash, Hasher},
+};
+
+/// Hex in axial coordinates (q, r). The third cube coordinate is s = -q - r.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub struct HexCoord {
+    pub q: i64,
+    pub r: i64,
+}
+
+/// Axial offsets of the six neighbours, clockwise starting from the east
+const HEX_DIRECTIONS: [(i64, i64); 6] = [(1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1), (1, -1)];
+
+/// Ways of laying hexes out in rows and columns, used to render hex grids (see
+/// https://www.redblobgames.com/grids/hexagons/#coordinates-offset).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum OffsetLayout {
+    /// Pointy-top hexes, odd rows shoved right
+    OddR,
+    /// Pointy-top hexes, even rows shoved right
+    EvenR,
+    /// Flat-top hexes, odd columns shoved down
+    OddQ,
+    /// Flat-top hexes, even columns shoved down
+    EvenQ,
+}
+
+impl HexCoord {
+    pub fn new(q: i64, r: i64) -> Self {
+        Self { q, r }
+    }
+
+    /// Builds a hex from cube coordinates. Panics if q + r + s != 0.
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Self {
+        assert_eq!(q + r + s, 0, "Cube coordinates must add up to 0");
+        Self { q, r }
+    }
+
+    pub fn s(&self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub fn add(&self, other: HexCoord) -> HexCoord {
+        HexCoord::new(self.q + other.q, self.r + other.r)
+    }
+
+    pub fn subtract(&self, other: HexCoord) -> HexCoord {
+        HexCoord::new(self.q - other.q, self.r - other.r)
+    }
+
+    pub fn scale(&self, factor: i64) -> HexCoord {
+        HexCoord::new(self.q * factor, self.r * factor)
+    }
+
+    /// Offset to the neighbour in the given direction (0 to 5, clockwise from the east)
+    pub fn direction(direction: usize) -> HexCoord {
+        let (q, r) = HEX_DIRECTIONS[direction % 6];
+        HexCoord::new(q, r)
+    }
+
+    pub fn neighbour(&self, direction: usize) -> HexCoord {
+        self.add(Self::direction(direction))
+    }
+
+    /// The six neighbours, clockwise starting from the east
+    pub fn neighbours(&self) -> Vec<HexCoord> {
+        (0..6).map(|d| self.neighbour(d)).collect()
+    }
+
+    /// Number of steps between two hexes
+    pub fn distance(&self, other: HexCoord) -> i64 {
+        let d = self.subtract(other);
+        (d.q.abs() + d.r.abs() + d.s().abs()) / 2
+    }
+
+    /// Hexes at exactly the given distance, in clockwise order
+    pub fn ring(&self, radius: i64) -> Vec<HexCoord> {
+        if radius == 0 {
+            return vec![*self];
+        }
+        let mut ring = vec![];
+        let mut hex = self.add(Self::direction(4).scale(radius));
+        for direction in 0..6 {
+            for _ in 0..radius {
+                ring.push(hex);
+                hex = hex.neighbour(direction);
+            }
+        }
+        ring
+    }
+
+    /// Hexes at distance at most the given radius, from the centre outwards
+    pub fn spiral(&self, radius: i64) -> Vec<HexCoord> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+
+    /// Hexes on the straight line between two hexes, both included
+    pub fn line_to(&self, other: HexCoord) -> Vec<HexCoord> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![*self];
+        }
+        // Nudge the endpoints so that points exactly on an edge round consistently
+        let (aq, ar) = (self.q as f64 + 1e-6, self.r as f64 + 1e-6);
+        let (bq, br) = (other.q as f64 + 1e-6, other.r as f64 + 1e-6);
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / n as f64;
+                Self::round(aq + (bq - aq) * t, ar + (br - ar) * t)
+            })
+            .collect()
+    }
+
+    /// Rounds fractional axial coordinates to the nearest hex
+    fn round(q: f64, r: f64) -> HexCoord {
+        let s = -q - r;
+        let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+        let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+        if dq > dr && dq > ds {
+            rq = -rr - rs;
+        } else if dr > ds {
+            rr = -rq - rs;
+        }
+        HexCoord::new(rq as i64, rr as i64)
+    }
+
+    /// Rotates 60 degrees clockwise around the origin
+    pub fn rotate_right(&self) -> HexCoord {
+        HexCoord::new(-self.r, -self.s())
+    }
+
+    /// Rotates 60 degrees counter-clockwise around the origin
+    pub fn rotate_left(&self) -> HexCoord {
+        HexCoord::new(-self.s(), -self.q)
+    }
+
+    /// Rotates clockwise around a centre hex by the given number of 60 degree steps
+    pub fn rotate_around(&self, center: HexCoord, steps: usize) -> HexCoord {
+        let mut hex = self.subtract(center);
+        for _ in 0..steps % 6 {
+            hex = hex.rotate_right();
+        }
+        hex.add(center)
+    }
+
+    /// Reflects across the q axis (keeps q, swaps r and s)
+    pub fn reflect_q(&self) -> HexCoord {
+        HexCoord::from_cube(self.q, self.s(), self.r)
+    }
+
+    /// Reflects across the r axis (keeps r, swaps q and s)
+    pub fn reflect_r(&self) -> HexCoord {
+        HexCoord::from_cube(self.s(), self.r, self.q)
+    }
+
+    /// Reflects across the s axis (keeps s, swaps q and r)
+    pub fn reflect_s(&self) -> HexCoord {
+        HexCoord::from_cube(self.r, self.q, self.s())
+    }
+
+    /// Converts to offset coordinates (column, row) in the given layout
+    pub fn to_offset(&self, layout: OffsetLayout) -> (i64, i64) {
+        let (q, r) = (self.q, self.r);
+        match layout {
+            OffsetLayout::OddR => (q + (r - (r & 1)) / 2, r),
+            OffsetLayout::EvenR => (q + (r + (r & 1)) / 2, r),
+            OffsetLayout::OddQ => (q, r + (q - (q & 1)) / 2),
+            OffsetLayout::EvenQ => (q, r + (q + (q & 1)) / 2),
+        }
+    }
+
+    /// Converts from offset coordinates (column, row) in the given layout
+    pub fn from_offset(col: i64, row: i64, layout: OffsetLayout) -> HexCoord {
+        match layout {
+            OffsetLayout::OddR => HexCoord::new(col - (row - (row & 1)) / 2, row),
+            OffsetLayout::EvenR => HexCoord::new(col - (row + (row & 1)) / 2, row),
+            OffsetLayout::OddQ => HexCoord::new(col, row - (col - (col & 1)) / 2),
+            OffsetLayout::EvenQ => HexCoord::new(col, row - (col + (col & 1)) / 2),
+        }
+    }
+}
+
+/// Board made of hexagonal cells, each of which may hold a component.
+#[derive(Debug, Clone)]
+pub struct HexBoard<T: Component + Clone> {
+    base: BaseComponent,
+    cells: BTreeMap<HexCoord, Option<T>>,
+    visibility_mode: VisibilityMode,
+}
+
+impl<T: Component + Clone> HexBoard<T> {
+    /// Board with the given hexes, all empty
+    pub fn new(hexes: Vec<HexCoord>, ids: &mut IdAllocator) -> Self {
+        Self {
+            base: BaseComponent::new_with_name(ComponentType::Board, "HexBoard", ids),
+            cells: hexes.into_iter().map(|h| (h, None)).collect(),
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn new_with_id(hexes: Vec<HexCoord>, id: usize) -> Self {
+        Self {
+            base: BaseComponent::new_with_name_and_id(ComponentType::Board, "HexBoard", id),
+            cells: hexes.into_iter().map(|h| (h, None)).collect(),
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    /// Hexagon-shaped board centred on the origin
+    pub fn new_hexagon(radius: i64, ids: &mut IdAllocator) -> Self {
+        Self::new(HexCoord::default().spiral(radius), ids)
+    }
+
+    /// Rectangular board of width x height hexes in offset coordinates
+    pub fn new_rectangle(
+        width: i64,
+        height: i64,
+        layout: OffsetLayout,
+        ids: &mut IdAllocator,
+    ) -> Self {
+        let hexes = (0..height)
+            .flat_map(|row| (0..width).map(move |col| HexCoord::from_offset(col, row, layout)))
+            .collect();
+        Self::new(hexes, ids)
+    }
+
+    pub fn contains(&self, hex: HexCoord) -> bool {
+        self.cells.contains_key(&hex)
+    }
+
+    /// Every hex of the board, in coordinate order
+    pub fn hexes(&self) -> Vec<HexCoord> {
+        self.cells.keys().copied().collect()
+    }
+
+    pub fn n_hexes(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns the component in the hex, or None if it is empty or not on the board
+    pub fn get(&self, hex: HexCoord) -> Option<&T> {
+        self.cells.get(&hex).and_then(|c| c.as_ref())
+    }
+
+    pub fn get_mut(&mut self, hex: HexCoord) -> Option<&mut T> {
+        self.cells.get_mut(&hex).and_then(|c| c.as_mut())
+    }
+
+    /// Puts a component in the hex. Returns false if the hex is not on the board.
+    pub fn set(&mut self, hex: HexCoord, value: T) -> bool {
+        match self.cells.get_mut(&hex) {
+            Some(cell) => {
+                *cell = Some(value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear_cell(&mut self, hex: HexCoord) -> Option<T> {
+        self.cells.get_mut(&hex).and_then(|c| c.take())
+    }
+
+    /// Neighbours of the hex that are on the board
+    pub fn neighbours(&self, hex: HexCoord) -> Vec<HexCoord> {
+        hex.neighbours()
+            .into_iter()
+            .filter(|h| self.contains(*h))
+            .collect()
+    }
+
+    /// Returns true if every hex strictly between the two is on the board and not blocked
+    pub fn line_of_sight_by(
+        &self,
+        from: HexCoord,
+        to: HexCoord,
+        blocks: impl Fn(&T) -> bool,
+    ) -> bool {
+        let line = from.line_to(to);
+        line.iter()
+            .skip(1)
+            .take(line.len().saturating_sub(2))
+            .all(|h| self.contains(*h) && !self.get(*h).is_some_and(&blocks))
+    }
+
+    /// Returns true if every hex strictly between the two is on the board and empty
+    pub fn line_of_sight(&self, from: HexCoord, to: HexCoord) -> bool {
+        self.line_of_sight_by(from, to, |_| true)
+    }
+}
+
+impl<T: Component + Clone> Component for HexBoard<T> {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        self.get_components()
+    }
+}
+
+impl<T: Component + Clone> IComponentContainer for HexBoard<T> {
+    fn get_components(&self) -> Vec<Box<dyn Component>> {
+        self.cells
+            .values()
+            .flatten()
+            .map(|c| Box::new(c.clone()) as Box<dyn Component>)
+            .collect()
+    }
+
+    fn get_visibility_mode(&self) -> VisibilityMode {
+        self.visibility_mode.clone()
+    }
+
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode) {
+        self.visibility_mode = visibility_mode;
+    }
+
+    fn set_owner_of(&mut self, component_id: usize, owner_id: i32) -> Option<i32> {
+        ownership::set_owner_among(
+            self.cells
+                .values_mut()
+                .flatten()
+                .map(|c| c as &mut dyn Component),
+            component_id,
+            owner_id,
+        )
+    }
+}
+
+impl<T: Component + Clone> HexBoard<T> {
+    // One line per occupied hex, e.g. "(1, -1): X", with the components not shown as '?'
+    fn render(&self, show: impl Fn(&T) -> bool) -> String {
+        let mut rendered = String::new();
+        for (hex, c) in self
+            .cells
+            .iter()
+            .filter_map(|(h, c)| c.as_ref().map(|c| (h, c)))
+        {
+            let c = if show(c) {
+                c.short_string()
+            } else {
+                "?".to_string()
+            };
+            rendered.push_str(&format!("({}, {}): {}\n", hex.q, hex.r, c));
+        }
+        rendered
+    }
+}
+
+impl<T: Component + Clone> IRenderable for HexBoard<T> {
+    /// Name and size, e.g. "HexBoard (19 hexes)"
+    fn short_string(&self) -> String {
+        format!("{} ({} hexes)", self.base.component_name(), self.n_hexes())
+    }
+
+    fn detailed_string(&self) -> String {
+        self.render(|_| true)
+    }
+
+    fn string_for_player(&self, gs: &dyn GameState, player: usize) -> String {
+        let components = self.get_components();
+        let hidden: Vec<usize> = visibility::hidden_indices(gs, self, player)
+            .iter()
+            .map(|i| components[*i].component_id())
+            .collect();
+        self.render(|c| !hidden.contains(&c.component_id()))
+    }
+}
+
+impl<T: Component + Clone> Hash for HexBoard<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.component_id().hash(state);
+        for (hex, cell) in &self.cells {
+            hex.hash(state);
+            cell.as_ref().map(|c| c.component_id()).hash(state);
+        }
+    }
+}
+
+impl<T: Component + Clone> PartialEq for HexBoard<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.component_id() == other.component_id()
+            && self
+                .cells
+                .iter()
+                .map(|(h, c)| (h, c.as_ref().map(|c| c.component_id())))
+                .eq(other
+                    .cells
+                    .iter()
+                    .map(|(h, c)| (h, c.as_ref().map(|c| c.component_id()))))
+    }
+}
+
+impl<T: Component + Clone> Eq for HexBoard<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::components::token::Token;
+
+    #[test]
+    fn test_distance_and_rings() {
+        let origin = HexCoord::default();
+        assert_eq!(origin.distance(HexCoord::new(2, -1)), 2);
+        assert_eq!(HexCoord::new(-3, 1).distance(HexCoord::new(2, -1)), 5);
+
+        assert_eq!(origin.ring(1).len(), 6);
+        assert_eq!(origin.ring(3).len(), 18);
+        assert!(origin.ring(3).iter().all(|h| origin.distance(*h) == 3));
+        assert_eq!(origin.spiral(2).len(), 19);
+        assert_eq!(origin.neighbours()[0], HexCoord::new(1, 0));
+        assert!(origin
+            .ring(1)
+            .iter()
+            .all(|h| origin.neighbours().contains(h)));
+    }
+
+    #[test]
+    fn test_line() {
+        let a = HexCoord::new(0, 0);
+        let b = HexCoord::new(3, -1);
+        let line = a.line_to(b);
+        assert_eq!(line.len(), 4);
+        assert_eq!(line[0], a);
+        assert_eq!(line[3], b);
+        assert!(line.windows(2).all(|w| w[0].distance(w[1]) == 1));
+    }
+
+    #[test]
+    fn test_rotation_and_reflection() {
+        let hex = HexCoord::new(2, -3);
+        assert_eq!(hex.rotate_right(), HexCoord::new(3, -1));
+        assert_eq!(hex.rotate_right().rotate_left(), hex);
+        assert_eq!(hex.rotate_around(HexCoord::default(), 6), hex);
+        assert_eq!(hex.rotate_around(HexCoord::default(), 3), hex.scale(-1));
+        let center = HexCoord::new(1, 1);
+        assert_eq!(
+            hex.rotate_around(center, 2).distance(center),
+            hex.distance(center)
+        );
+
+        assert_eq!(hex.reflect_q(), HexCoord::new(2, 1));
+        assert_eq!(hex.reflect_q().reflect_q(), hex);
+        assert_eq!(hex.reflect_r().r, hex.r);
+        assert_eq!(hex.reflect_s().s(), hex.s());
+    }
+
+    #[test]
+    fn test_offset_conversion() {
+        for layout in [
+            OffsetLayout::OddR,
+            OffsetLayout::EvenR,
+            OffsetLayout::OddQ,
+            OffsetLayout::EvenQ,
+        ] {
+            for hex in HexCoord::default().spiral(4) {
+                let (col, row) = hex.to_offset(layout);
+                assert_eq!(HexCoord::from_offset(col, row, layout), hex);
+            }
+        }
+        assert_eq!(HexCoord::new(-1, 3).to_offset(OffsetLayout::OddR), (0, 3));
+        assert_eq!(HexCoord::new(3, -1).to_offset(OffsetLayout::EvenQ), (3, 1));
+    }
+
+    #[test]
+    fn test_board() {
+        let mut board: HexBoard<Token> = HexBoard::new_hexagon(2, &mut IdAllocator::new());
+        assert_eq!(board.n_hexes(), 19);
+        assert_eq!(board.neighbours(HexCoord::new(2, 0)).len(), 3);
+        assert!(!board.set(HexCoord::new(3, 0), Token::new_with_id("Stone", 1)));
+
+        let from = HexCoord::new(-2, 0);
+        let to = HexCoord::new(2, 0);
+        assert!(board.line_of_sight(from, to));
+        assert!(board.set(HexCoord::new(0, 0), Token::new_with_id("Stone", 1)));
+        assert!(!board.line_of_sight(from, to));
+        assert!(board.line_of_sight_by(from, to, |t| t.token_type() == "Wall"));
+        assert_eq!(board.get_size(), 1);
+
+        let rectangle: HexBoard<Token> =
+            HexBoard::new_rectangle(4, 3, OffsetLayout::OddR, &mut IdAllocator::new());
+        assert_eq!(rectangle.n_hexes(), 12);
+    }
+}
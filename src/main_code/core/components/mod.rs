@@ -1,3 +1,13 @@
 pub mod area;
+pub mod card;
 pub mod component;
+pub mod component_loader;
+pub mod counter;
+pub mod deck;
+pub mod dice;
+pub mod graph_board;
+pub mod grid_board;
+pub mod hex_board;
+pub mod partial_observable_deck;
 pub mod token;
+pub mod token_pool;
@@ -0,0 +1,485 @@
+use crate::main_code::core::{
+    components::{
+        component::{BaseComponent, Component},
+        deck::Deck,
+    },
+    core_constants::VisibilityMode,
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    interfaces::{
+        component_container::IComponentContainer,
+        renderable::{self, IRenderable},
+    },
+};
+use rand::{seq::SliceRandom, Rng, RngCore};
+use std::hash::{Hash, Hasher};
+
+/// Deck where each card can be visible to a different subset of players. Visibility is tracked
+/// per position: `element_visibility[i][p]` is true if player `p` can see the card at index `i`.
+#[derive(Debug, Clone)]
+pub struct PartialObservableDeck<T: Component + Clone> {
+    deck: Deck<T>,
+    element_visibility: Vec<Vec<bool>>,
+    // Visibility given to cards added without an explicit one, and to every card after a shuffle
+    default_visibility: Vec<bool>,
+}
+
+impl<T: Component + Clone> PartialObservableDeck<T> {
+    pub fn new(
+        name: &str,
+        owner: i32,
+        default_visibility: Vec<bool>,
+        ids: &mut IdAllocator,
+    ) -> Self {
+        Self {
+            deck: Deck::new(name, owner, VisibilityMode::MixedVisibility, ids),
+            element_visibility: vec![],
+            default_visibility,
+        }
+    }
+
+    pub fn new_with_id(name: &str, owner: i32, id: usize, default_visibility: Vec<bool>) -> Self {
+        Self {
+            deck: Deck::new_with_id(name, owner, id, VisibilityMode::MixedVisibility),
+            element_visibility: vec![],
+            default_visibility,
+        }
+    }
+
+    pub fn deck(&self) -> &Deck<T> {
+        &self.deck
+    }
+
+    pub fn n_players(&self) -> usize {
+        self.default_visibility.len()
+    }
+
+    pub fn default_visibility(&self) -> &Vec<bool> {
+        &self.default_visibility
+    }
+
+    pub fn set_capacity(&mut self, capacity: Option<usize>) {
+        self.deck.set_capacity(capacity);
+    }
+
+    pub fn len(&self) -> usize {
+        self.deck.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deck.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.deck.get(index)
+    }
+
+    pub fn index_of(&self, component_id: usize) -> Option<usize> {
+        self.deck.index_of(component_id)
+    }
+
+    /// Adds a component to the top of the deck with the default visibility
+    pub fn add(&mut self, c: T) -> bool {
+        self.add_at(0, c, self.default_visibility.clone())
+    }
+
+    /// Adds a component to the top of the deck, visible to the given players
+    pub fn add_with_visibility(&mut self, c: T, visibility: Vec<bool>) -> bool {
+        self.add_at(0, c, visibility)
+    }
+
+    /// Adds a component to the bottom of the deck with the default visibility
+    pub fn add_to_bottom(&mut self, c: T) -> bool {
+        self.add_at(self.len(), c, self.default_visibility.clone())
+    }
+
+    /// Adds a component to the bottom of the deck, visible to the given players
+    pub fn add_to_bottom_with_visibility(&mut self, c: T, visibility: Vec<bool>) -> bool {
+        self.add_at(self.len(), c, visibility)
+    }
+
+    /// Inserts a component at the given index, visible to the given players. Returns false if the
+    /// deck is full, the index is greater than the size of the deck or the visibility is not
+    /// given for every player.
+    pub fn add_at(&mut self, index: usize, c: T, visibility: Vec<bool>) -> bool {
+        if visibility.len() != self.n_players() || !self.deck.add_at(index, c) {
+            return false;
+        }
+        self.element_visibility.insert(index, visibility);
+        true
+    }
+
+    /// Removes and returns the top component
+    pub fn draw(&mut self) -> Option<T> {
+        self.remove_at(0)
+    }
+
+    /// Returns the top component without removing it or changing its visibility
+    pub fn peek(&self) -> Option<&T> {
+        self.deck.peek()
+    }
+
+    /// The player looks at the component at the given index, which becomes visible to them
+    pub fn peek_for_player(&mut self, index: usize, player: usize) -> Option<&T> {
+        if index >= self.len() {
+            return None;
+        }
+        self.set_visibility_of_component(index, player, true);
+        self.deck.get(index)
+    }
+
+    pub fn remove_at(&mut self, index: usize) -> Option<T> {
+        let c = self.deck.remove_at(index)?;
+        self.element_visibility.remove(index);
+        Some(c)
+    }
+
+    pub fn remove_by_id(&mut self, component_id: usize) -> Option<T> {
+        self.index_of(component_id).and_then(|i| self.remove_at(i))
+    }
+
+    pub fn clear(&mut self) {
+        self.deck.clear();
+        self.element_visibility.clear();
+    }
+
+    /// Returns true if the player can see the component at the given index, false if either is
+    /// out of range
+    pub fn is_visible(&self, index: usize, player: usize) -> bool {
+        self.is_visible_at(index, player)
+    }
+
+    /// Players that can see the component at the given index, or None if there is no component
+    pub fn visibility_of_component(&self, index: usize) -> Option<&Vec<bool>> {
+        self.element_visibility.get(index)
+    }
+
+    /// Returns false, changing nothing, if the index or the player is out of range
+    pub fn set_visibility_of_component(
+        &mut self,
+        index: usize,
+        player: usize,
+        visible: bool,
+    ) -> bool {
+        match self
+            .element_visibility
+            .get_mut(index)
+            .and_then(|v| v.get_mut(player))
+        {
+            Some(v) => {
+                *v = visible;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reveals the component at the given index to every player. Returns false if there is no
+    /// component at that index.
+    pub fn reveal_to_all(&mut self, index: usize) -> bool {
+        let n_players = self.n_players();
+        match self.element_visibility.get_mut(index) {
+            Some(v) => {
+                *v = vec![true; n_players];
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Same as set_visibility_mode, but VisibleToOwner also shows the cards to the players that
+    /// can observe the owner's information (see GameState::can_observe_owner), e.g. teammates
+    /// sharing information.
+    pub fn set_visibility_mode_for(&mut self, gs: &dyn GameState, visibility_mode: VisibilityMode) {
+        let owner = self.owner_id();
+        self.apply_visibility_mode(visibility_mode, |player| {
+            gs.can_observe_owner(player, owner)
+        });
+    }
+
+    // Applies a uniform mode to every card, with the given players seeing the cards in
+    // VisibleToOwner mode
+    fn apply_visibility_mode(
+        &mut self,
+        visibility_mode: VisibilityMode,
+        observes_owner: impl Fn(usize) -> bool,
+    ) {
+        if visibility_mode == VisibilityMode::MixedVisibility {
+            return;
+        }
+        let len = self.len();
+        let visible = |index: usize, player: usize| match visibility_mode {
+            VisibilityMode::VisibleToAll => true,
+            VisibilityMode::HiddenToAll => false,
+            VisibilityMode::VisibleToOwner => observes_owner(player),
+            VisibilityMode::FirstVisibleToAll => index == 0,
+            VisibilityMode::LastVisibleToAll => index + 1 == len,
+            VisibilityMode::MixedVisibility => unreachable!(),
+        };
+        let n_players = self.n_players();
+        self.element_visibility = (0..len)
+            .map(|i| (0..n_players).map(|p| visible(i, p)).collect())
+            .collect();
+        if !matches!(
+            visibility_mode,
+            VisibilityMode::FirstVisibleToAll | VisibilityMode::LastVisibleToAll
+        ) {
+            self.default_visibility = (0..n_players).map(|p| visible(0, p)).collect();
+        }
+    }
+
+    /// Returns the components as seen by the player: None for those the player can't see
+    pub fn visible_components(&self, player: usize) -> Vec<Option<&T>> {
+        self.deck
+            .components()
+            .iter()
+            .zip(&self.element_visibility)
+            .map(|(c, v)| if v[player] { Some(c) } else { None })
+            .collect()
+    }
+
+    /// Shuffles the deck. Players lose track of the cards they had seen, so every card goes back
+    /// to the default visibility.
+    pub fn shuffle<R: Rng + ?Sized>(&mut self, rnd: &mut R) {
+        self.deck.shuffle(rnd);
+        self.element_visibility = vec![self.default_visibility.clone(); self.len()];
+    }
+
+    /// Shuffles the cards the player can't see among their positions, leaving the visible ones
+    /// (and the visibility of every position) untouched.
+    pub fn redeterminise_unknown<R: Rng + ?Sized>(&mut self, player: usize, rnd: &mut R) {
+        let hidden: Vec<usize> = (0..self.len())
+            .filter(|i| !self.is_visible(*i, player))
+            .collect();
+        let mut shuffled = hidden.clone();
+        shuffled.shuffle(rnd);
+
+        let original = self.deck.components().clone();
+        for (to, from) in hidden.iter().zip(&shuffled) {
+            *self.deck.get_mut(*to).unwrap() = original[*from].clone();
+        }
+    }
+
+    /// Copy of the deck as the player could believe it to be: the cards the player can't see are
+    /// redeterminised. Takes the same arguments as Deck::copy_for_player, although the deck keeps
+    /// track of what each player has seen without the game state.
+    pub fn copy_for_player<R: Rng + ?Sized>(
+        &self,
+        _gs: &dyn GameState,
+        player: usize,
+        rnd: &mut R,
+    ) -> Self {
+        let mut copy = self.clone();
+        copy.redeterminise_unknown(player, rnd);
+        copy
+    }
+}
+
+impl<T: Component + Clone> Component for PartialObservableDeck<T> {
+    fn base(&self) -> &BaseComponent {
+        self.deck.base()
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        self.deck.base_mut()
+    }
+
+    fn perspective_copy(
+        &self,
+        gs: &dyn GameState,
+        player: usize,
+        rnd: &mut dyn RngCore,
+    ) -> Option<Box<dyn Component>> {
+        Some(Box::new(self.copy_for_player(gs, player, rnd)))
+    }
+
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        self.get_components()
+    }
+}
+
+impl<T: Component + Clone> IComponentContainer for PartialObservableDeck<T> {
+    fn get_components(&self) -> Vec<Box<dyn Component>> {
+        self.deck.get_components()
+    }
+
+    fn get_visibility_mode(&self) -> VisibilityMode {
+        VisibilityMode::MixedVisibility
+    }
+
+    /// Applies a uniform mode to every card, which can then be changed card by card; the deck
+    /// itself stays in MixedVisibility mode. Modes that do not depend on the position also become
+    /// the default visibility. Without a game state, VisibleToOwner shows the cards to the owner
+    /// only; use set_visibility_mode_for to include the players that can observe the owner.
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode) {
+        let owner = self.owner_id();
+        self.apply_visibility_mode(visibility_mode, |player| player as i32 == owner);
+    }
+
+    fn set_owner_of(&mut self, component_id: usize, owner_id: i32) -> Option<i32> {
+        self.deck.set_owner_of(component_id, owner_id)
+    }
+
+    fn is_visible_at(&self, index: usize, player: usize) -> bool {
+        self.element_visibility
+            .get(index)
+            .and_then(|v| v.get(player))
+            .copied()
+            .unwrap_or(false)
+    }
+}
+
+impl<T: Component + Clone> IRenderable for PartialObservableDeck<T> {
+    fn short_string(&self) -> String {
+        self.deck.short_string()
+    }
+
+    fn detailed_string(&self) -> String {
+        self.deck.detailed_string()
+    }
+
+    fn string_for_player(&self, gs: &dyn GameState, player: usize) -> String {
+        format!(
+            "{}: [{}]",
+            self.deck.base().component_name(),
+            renderable::contents_for_player(gs, self, player).join(", ")
+        )
+    }
+}
+
+impl<T: Component + Clone> Hash for PartialObservableDeck<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deck.hash(state);
+        self.element_visibility.hash(state);
+    }
+}
+
+impl<T: Component + Clone> PartialEq for PartialObservableDeck<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.deck == other.deck && self.element_visibility == other.element_visibility
+    }
+}
+
+impl<T: Component + Clone> Eq for PartialObservableDeck<T> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        components::token::Token, game_parameters::AbstractParameters,
+        game_state::AbstractGameState,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn ids(deck: &PartialObservableDeck<Token>) -> Vec<usize> {
+        deck.deck()
+            .components()
+            .iter()
+            .map(|c| c.component_id())
+            .collect()
+    }
+
+    fn deck_of(n: usize) -> PartialObservableDeck<Token> {
+        let mut deck = PartialObservableDeck::new_with_id("Deck", -1, 100, vec![false, false]);
+        for i in 0..n {
+            deck.add_to_bottom(Token::new_with_id("Card", i));
+        }
+        deck
+    }
+
+    #[test]
+    fn test_visibility_updates() {
+        let mut deck = deck_of(3);
+        deck.add_with_visibility(Token::new_with_id("Card", 3), vec![true, false]);
+        assert!(deck.is_visible(0, 0));
+        assert!(!deck.is_visible(0, 1));
+
+        assert_eq!(deck.peek_for_player(2, 1).unwrap().component_id(), 1);
+        assert!(deck.is_visible(2, 1));
+        assert!(!deck.is_visible(2, 0));
+
+        deck.reveal_to_all(3);
+        let seen: Vec<bool> = deck
+            .visible_components(0)
+            .iter()
+            .map(|c| c.is_some())
+            .collect();
+        assert_eq!(seen, vec![true, false, false, true]);
+
+        assert_eq!(deck.draw().unwrap().component_id(), 3);
+        assert!(!deck.is_visible(0, 0));
+        assert!(deck.is_visible(1, 1));
+
+        deck.shuffle(&mut StdRng::seed_from_u64(0));
+        assert!((0..3).all(|i| !deck.is_visible(i, 0) && !deck.is_visible(i, 1)));
+        assert_eq!(deck.get_visibility_mode(), VisibilityMode::MixedVisibility);
+    }
+
+    #[test]
+    fn test_copy_for_player_keeps_visible_cards() {
+        let mut deck = deck_of(30);
+        deck.peek_for_player(4, 0);
+        deck.peek_for_player(17, 0);
+        deck.peek_for_player(9, 1);
+
+        let gs = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let copy = deck.copy_for_player(&gs, 0, &mut StdRng::seed_from_u64(3));
+        assert_eq!(copy.get(4).unwrap().component_id(), 4);
+        assert_eq!(copy.get(17).unwrap().component_id(), 17);
+        assert_ne!(ids(&copy), ids(&deck));
+
+        let mut sorted = ids(&copy);
+        sorted.sort();
+        assert_eq!(sorted, ids(&deck));
+        assert_eq!(
+            copy.element_visibility, deck.element_visibility,
+            "Redetermination must not change what each player knows"
+        );
+    }
+
+    #[test]
+    fn test_uniform_visibility_modes() {
+        let mut deck = deck_of(3);
+        deck.set_visibility_mode(VisibilityMode::LastVisibleToAll);
+        assert!(deck.is_visible(2, 0) && deck.is_visible(2, 1));
+        assert!(!deck.is_visible(0, 0) && !deck.is_visible(1, 1));
+
+        let mut gs = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        gs.set_teams(vec![0, 0]);
+        gs.set_share_team_information(true);
+        deck.set_owner_id(1);
+        deck.set_visibility_mode(VisibilityMode::VisibleToOwner);
+        assert!(!deck.is_visible(0, 0) && deck.is_visible(0, 1));
+        deck.set_visibility_mode_for(&gs, VisibilityMode::VisibleToOwner);
+        assert!(deck.is_visible(0, 0) && deck.is_visible(0, 1));
+
+        deck.set_visibility_mode(VisibilityMode::VisibleToAll);
+        assert!((0..3).all(|i| deck.is_visible(i, 0) && deck.is_visible(i, 1)));
+        assert_eq!(deck.default_visibility(), &vec![true, true]);
+        assert_eq!(deck.get_visibility_mode(), VisibilityMode::MixedVisibility);
+    }
+
+    #[test]
+    fn test_out_of_range_visibility() {
+        let mut deck = deck_of(2);
+        assert!(!deck.is_visible(2, 0));
+        assert!(!deck.is_visible(0, 2));
+        assert_eq!(deck.visibility_of_component(2), None);
+        assert!(!deck.set_visibility_of_component(0, 2, true));
+        assert!(!deck.set_visibility_of_component(2, 0, true));
+        assert!(!deck.reveal_to_all(2));
+        assert!(deck.reveal_to_all(1));
+        assert_eq!(deck.visibility_of_component(1), Some(&vec![true, true]));
+
+        assert!(!deck.add_at(0, Token::new_with_id("Card", 2), vec![true]));
+        assert_eq!(deck.len(), 2);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::main_code::core::{
     components::component::{BaseComponent, Component},
     core_constants::ComponentType,
+    id_allocator::IdAllocator,
+    interfaces::renderable::IRenderable,
+    properties::{Properties, PropertyKey, PropertyValue},
 };
 use std::fmt;
 
@@ -11,10 +14,10 @@ pub struct Token {
 }
 
 impl Token {
-    pub fn new(token_type: &str) -> Self {
+    pub fn new(token_type: &str, ids: &mut IdAllocator) -> Self {
         Self {
             token_type: token_type.to_string(),
-            base: BaseComponent::new_with_name(ComponentType::Token, token_type),
+            base: BaseComponent::new_with_name(ComponentType::Token, token_type, ids),
         }
     }
     pub fn new_with_id(token_type: &str, id: usize) -> Self {
@@ -26,11 +29,27 @@ impl Token {
     pub fn token_type(&self) -> &str {
         &self.token_type
     }
+
+    pub fn properties(&self) -> &Properties {
+        self.base.properties()
+    }
+
+    pub fn get_property(&self, key: impl Into<PropertyKey>) -> Option<&PropertyValue> {
+        self.base.get_property(key)
+    }
+
+    pub fn set_property(&mut self, name: &str, value: PropertyValue) -> Option<PropertyValue> {
+        self.base.set_property(name, value)
+    }
 }
 
 impl Component for Token {
-    fn component_id(&self) -> usize {
-        self.base.component_id()
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
     }
 
     fn nested_components(&self) -> Vec<Box<dyn Component>> {
@@ -38,6 +57,24 @@ impl Component for Token {
     }
 }
 
+impl IRenderable for Token {
+    fn short_string(&self) -> String {
+        self.token_type.clone()
+    }
+
+    /// Type, owner (if any) and properties
+    fn detailed_string(&self) -> String {
+        let mut s = self.token_type.clone();
+        if self.owner_id() >= 0 {
+            s.push_str(&format!(" (player {})", self.owner_id()));
+        }
+        if !self.properties().is_empty() {
+            s.push_str(&format!(" {}", self.properties()));
+        }
+        s
+    }
+}
+
 impl fmt::Display for Token {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.token_type)
@@ -0,0 +1,654 @@
+use crate::main_code::core::{
+    components::component::{BaseComponent, Component},
+    core_constants::{ComponentType, VisibilityMode},
+    game_state::GameState,
+    id_allocator::IdAllocator,
+    interfaces::{component_container::IComponentContainer, renderable::IRenderable},
+    ownership, visibility,
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// Errors produced when moving fungible tokens around.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TokenPoolError {
+    NotEnoughTokens {
+        token_type: String,
+        available: u64,
+        requested: u64,
+    },
+    UnknownHolder(i32),
+    ConservationViolated {
+        token_type: String,
+        expected: u64,
+        found: u64,
+    },
+}
+
+impl fmt::Display for TokenPoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenPoolError::NotEnoughTokens {
+                token_type,
+                available,
+                requested,
+            } => write!(
+                f,
+                "not enough '{}' tokens: {} requested, {} available",
+                token_type, requested, available
+            ),
+            TokenPoolError::UnknownHolder(holder) => write!(f, "unknown token holder {}", holder),
+            TokenPoolError::ConservationViolated {
+                token_type,
+                expected,
+                found,
+            } => write!(
+                f,
+                "'{}' tokens are not conserved: expected {}, found {}",
+                token_type, expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TokenPoolError {}
+
+/// Stack of fungible tokens (coins, cubes...), stored as a count per token type instead of one
+/// component per token. It holds no individual components, so get_size is 0; use total for the
+/// number of tokens.
+#[derive(Debug, Clone)]
+pub struct TokenPool {
+    base: BaseComponent,
+    // Types with no tokens left are removed, so equal pools have equal maps
+    counts: BTreeMap<String, u64>,
+    visibility_mode: VisibilityMode,
+}
+
+impl TokenPool {
+    pub fn new(owner: i32, ids: &mut IdAllocator) -> Self {
+        let mut base = BaseComponent::new_with_name(ComponentType::TokenPool, "TokenPool", ids);
+        base.set_owner_id(owner);
+        Self {
+            base,
+            counts: BTreeMap::new(),
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn new_with_id(owner: i32, id: usize) -> Self {
+        let mut base =
+            BaseComponent::new_with_name_and_id(ComponentType::TokenPool, "TokenPool", id);
+        base.set_owner_id(owner);
+        Self {
+            base,
+            counts: BTreeMap::new(),
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn count(&self, token_type: &str) -> u64 {
+        self.counts.get(token_type).copied().unwrap_or(0)
+    }
+
+    pub fn counts(&self) -> &BTreeMap<String, u64> {
+        &self.counts
+    }
+
+    /// Total number of tokens, of every type
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    pub fn has(&self, token_type: &str, amount: u64) -> bool {
+        self.count(token_type) >= amount
+    }
+
+    /// Returns true if the pool holds at least the given amount of each type
+    pub fn has_all(&self, amounts: &[(&str, u64)]) -> bool {
+        Self::group(amounts).iter().all(|(t, n)| self.has(t, *n))
+    }
+
+    pub fn add(&mut self, token_type: &str, amount: u64) {
+        if amount > 0 {
+            *self.counts.entry(token_type.to_string()).or_default() += amount;
+        }
+    }
+
+    /// Removes tokens, failing without changes if there are not enough
+    pub fn remove(&mut self, token_type: &str, amount: u64) -> Result<(), TokenPoolError> {
+        let available = self.count(token_type);
+        if available < amount {
+            return Err(TokenPoolError::NotEnoughTokens {
+                token_type: token_type.to_string(),
+                available,
+                requested: amount,
+            });
+        }
+        self.set_count(token_type, available - amount);
+        Ok(())
+    }
+
+    /// Removes the given amount of each type (e.g. to pay a cost), or nothing if any is missing
+    pub fn remove_all(&mut self, amounts: &[(&str, u64)]) -> Result<(), TokenPoolError> {
+        let required = Self::group(amounts);
+        if let Some((token_type, requested)) = required.iter().find(|(t, n)| !self.has(t, **n)) {
+            return Err(TokenPoolError::NotEnoughTokens {
+                token_type: token_type.to_string(),
+                available: self.count(token_type),
+                requested: *requested,
+            });
+        }
+        for (token_type, amount) in required {
+            self.remove(token_type, amount)?;
+        }
+        Ok(())
+    }
+
+    pub fn set_count(&mut self, token_type: &str, count: u64) {
+        if count == 0 {
+            self.counts.remove(token_type);
+        } else {
+            self.counts.insert(token_type.to_string(), count);
+        }
+    }
+
+    /// Moves tokens to another pool, failing without changes if there are not enough
+    pub fn transfer_to(
+        &mut self,
+        other: &mut TokenPool,
+        token_type: &str,
+        amount: u64,
+    ) -> Result<(), TokenPoolError> {
+        self.remove(token_type, amount)?;
+        other.add(token_type, amount);
+        Ok(())
+    }
+
+    // Sums the amounts given for the same type more than once
+    fn group<'a>(amounts: &[(&'a str, u64)]) -> BTreeMap<&'a str, u64> {
+        let mut grouped = BTreeMap::new();
+        for (token_type, amount) in amounts {
+            *grouped.entry(*token_type).or_default() += amount;
+        }
+        grouped
+    }
+}
+
+impl Component for TokenPool {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        vec![]
+    }
+}
+
+impl IComponentContainer for TokenPool {
+    /// Fungible tokens are not individual components
+    fn get_components(&self) -> Vec<Box<dyn Component>> {
+        vec![]
+    }
+
+    fn get_visibility_mode(&self) -> VisibilityMode {
+        self.visibility_mode.clone()
+    }
+
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode) {
+        self.visibility_mode = visibility_mode;
+    }
+
+    fn set_owner_of(&mut self, _component_id: usize, _owner_id: i32) -> Option<i32> {
+        // Fungible tokens are not individual components
+        None
+    }
+
+    fn total_count(&self) -> u64 {
+        self.total()
+    }
+}
+
+impl Hash for TokenPool {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.component_id().hash(state);
+        self.counts.hash(state);
+    }
+}
+
+impl PartialEq for TokenPool {
+    fn eq(&self, other: &Self) -> bool {
+        self.component_id() == other.component_id() && self.counts == other.counts
+    }
+}
+
+impl Eq for TokenPool {}
+
+impl IRenderable for TokenPool {
+    fn short_string(&self) -> String {
+        self.to_string()
+    }
+
+    /// Players who cannot see the pool only know how many tokens it holds, e.g. "{? x 5}"
+    fn string_for_player(&self, gs: &dyn GameState, player: usize) -> String {
+        if self.is_visible_to(gs, player) {
+            self.to_string()
+        } else {
+            self.hidden_string()
+        }
+    }
+}
+
+impl TokenPool {
+    // The counts have no positions, so only VisibleToAll and VisibleToOwner show them
+    fn is_visible_to(&self, gs: &dyn GameState, player: usize) -> bool {
+        match self.visibility_mode {
+            VisibilityMode::VisibleToAll => true,
+            VisibilityMode::VisibleToOwner => gs.can_observe_owner(player, self.owner_id()),
+            _ => false,
+        }
+    }
+
+    fn hidden_string(&self) -> String {
+        format!("{{? x {}}}", self.total())
+    }
+}
+
+impl fmt::Display for TokenPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts: Vec<String> = self
+            .counts
+            .iter()
+            .map(|(t, n)| format!("{}: {}", t, n))
+            .collect();
+        write!(f, "{{{}}}", counts.join(", "))
+    }
+}
+
+/// Token pools of every player plus a common supply, identified by owner ID (-1 for the supply).
+/// Tokens only enter or leave the game through the supply, so the total of each type is known
+/// and can be checked at any time.
+#[derive(Debug, Clone)]
+pub struct ResourceBank {
+    base: BaseComponent,
+    supply: TokenPool,
+    player_pools: Vec<TokenPool>,
+    // Number of tokens of each type that should exist across all pools
+    expected_totals: BTreeMap<String, u64>,
+    visibility_mode: VisibilityMode,
+}
+
+impl ResourceBank {
+    pub fn new(n_players: usize, ids: &mut IdAllocator) -> Self {
+        Self {
+            base: BaseComponent::new_with_name(ComponentType::ResourceBank, "ResourceBank", ids),
+            supply: TokenPool::new(-1, ids),
+            player_pools: (0..n_players)
+                .map(|p| TokenPool::new(p as i32, ids))
+                .collect(),
+            expected_totals: BTreeMap::new(),
+            visibility_mode: VisibilityMode::VisibleToAll,
+        }
+    }
+
+    pub fn n_players(&self) -> usize {
+        self.player_pools.len()
+    }
+
+    pub fn supply(&self) -> &TokenPool {
+        &self.supply
+    }
+
+    /// Pool of the given holder: a player index, or -1 for the supply
+    pub fn pool(&self, holder: i32) -> Option<&TokenPool> {
+        match holder {
+            -1 => Some(&self.supply),
+            p if p >= 0 => self.player_pools.get(p as usize),
+            _ => None,
+        }
+    }
+
+    /// Direct access to a pool. Changes made through it bypass the bank, so they may break the
+    /// conservation of tokens (see check_conservation).
+    pub fn pool_mut(&mut self, holder: i32) -> Option<&mut TokenPool> {
+        match holder {
+            -1 => Some(&mut self.supply),
+            p if p >= 0 => self.player_pools.get_mut(p as usize),
+            _ => None,
+        }
+    }
+
+    /// Brings new tokens into the game, in the supply
+    pub fn add_to_supply(&mut self, token_type: &str, amount: u64) {
+        self.supply.add(token_type, amount);
+        *self
+            .expected_totals
+            .entry(token_type.to_string())
+            .or_default() += amount;
+    }
+
+    /// Takes tokens out of the game, from the supply. Tokens put in the supply through pool_mut
+    /// were never counted, so the expected total does not go below 0.
+    pub fn remove_from_supply(
+        &mut self,
+        token_type: &str,
+        amount: u64,
+    ) -> Result<(), TokenPoolError> {
+        self.supply.remove(token_type, amount)?;
+        if let Some(total) = self.expected_totals.get_mut(token_type) {
+            *total = total.saturating_sub(amount);
+        }
+        Ok(())
+    }
+
+    /// Moves tokens between two holders (player indices, or -1 for the supply). Fails without
+    /// changes if either holder does not exist or the source does not have enough tokens.
+    pub fn transfer(
+        &mut self,
+        from: i32,
+        to: i32,
+        token_type: &str,
+        amount: u64,
+    ) -> Result<(), TokenPoolError> {
+        if self.pool(to).is_none() {
+            return Err(TokenPoolError::UnknownHolder(to));
+        }
+        self.pool_mut(from)
+            .ok_or(TokenPoolError::UnknownHolder(from))?
+            .remove(token_type, amount)?;
+        self.pool_mut(to).unwrap().add(token_type, amount);
+        Ok(())
+    }
+
+    /// Number of tokens of the given type, across the supply and every player
+    pub fn total(&self, token_type: &str) -> u64 {
+        self.pools().map(|p| p.count(token_type)).sum()
+    }
+
+    /// Checks that the number of tokens of each type is the one brought in through the supply
+    pub fn check_conservation(&self) -> Result<(), TokenPoolError> {
+        let mut token_types: Vec<&String> = self.expected_totals.keys().collect();
+        token_types.extend(self.pools().flat_map(|p| p.counts().keys()));
+        for token_type in token_types {
+            let expected = self.expected_totals.get(token_type).copied().unwrap_or(0);
+            let found = self.total(token_type);
+            if expected != found {
+                return Err(TokenPoolError::ConservationViolated {
+                    token_type: token_type.clone(),
+                    expected,
+                    found,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn pools(&self) -> impl Iterator<Item = &TokenPool> {
+        std::iter::once(&self.supply).chain(self.player_pools.iter())
+    }
+}
+
+impl Component for ResourceBank {
+    fn base(&self) -> &BaseComponent {
+        &self.base
+    }
+
+    fn base_mut(&mut self) -> &mut BaseComponent {
+        &mut self.base
+    }
+
+    fn as_container(&self) -> Option<&dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn as_container_mut(&mut self) -> Option<&mut dyn IComponentContainer> {
+        Some(self)
+    }
+
+    fn nested_components(&self) -> Vec<Box<dyn Component>> {
+        self.get_components()
+    }
+}
+
+impl IComponentContainer for ResourceBank {
+    /// The supply followed by the pool of each player
+    fn get_components(&self) -> Vec<Box<dyn Component>> {
+        self.pools()
+            .map(|p| Box::new(p.clone()) as Box<dyn Component>)
+            .collect()
+    }
+
+    fn get_visibility_mode(&self) -> VisibilityMode {
+        self.visibility_mode.clone()
+    }
+
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode) {
+        self.visibility_mode = visibility_mode;
+    }
+
+    fn set_owner_of(&mut self, component_id: usize, owner_id: i32) -> Option<i32> {
+        ownership::set_owner_among(
+            std::iter::once(&mut self.supply)
+                .chain(self.player_pools.iter_mut())
+                .map(|p| p as &mut dyn Component),
+            component_id,
+            owner_id,
+        )
+    }
+
+    /// Tokens of every type, across the supply and every player
+    fn total_count(&self) -> u64 {
+        self.pools().map(|p| p.total()).sum()
+    }
+}
+
+impl IRenderable for ResourceBank {
+    /// Totals of each type in the bank, e.g. "ResourceBank {Coin: 50}"
+    fn short_string(&self) -> String {
+        let mut totals: BTreeMap<&String, u64> = BTreeMap::new();
+        for (token_type, count) in self.pools().flat_map(|p| p.counts()) {
+            *totals.entry(token_type).or_default() += count;
+        }
+        let totals: Vec<String> = totals
+            .iter()
+            .map(|(t, n)| format!("{}: {}", t, n))
+            .collect();
+        format!("{} {{{}}}", self.base.component_name(), totals.join(", "))
+    }
+
+    /// One line for the supply and one per player
+    fn detailed_string(&self) -> String {
+        self.render(|_, p| p.detailed_string())
+    }
+
+    /// The mode of the bank hides whole pools (in get_components order), the pools it shows are
+    /// then rendered according to their own mode
+    fn string_for_player(&self, gs: &dyn GameState, player: usize) -> String {
+        self.render(|i, p| {
+            if visibility::is_visible_at(gs, self, i, player) {
+                p.string_for_player(gs, player)
+            } else {
+                p.hidden_string()
+            }
+        })
+    }
+}
+
+impl ResourceBank {
+    fn render(&self, render: impl Fn(usize, &TokenPool) -> String) -> String {
+        let mut rendered = format!("Supply: {}\n", render(0, &self.supply));
+        for (p, pool) in self.player_pools.iter().enumerate() {
+            rendered.push_str(&format!("Player {}: {}\n", p, render(p + 1, pool)));
+        }
+        rendered
+    }
+}
+
+impl Hash for ResourceBank {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.component_id().hash(state);
+        for pool in self.pools() {
+            pool.hash(state);
+        }
+    }
+}
+
+impl PartialEq for ResourceBank {
+    fn eq(&self, other: &Self) -> bool {
+        self.component_id() == other.component_id()
+            && self.supply == other.supply
+            && self.player_pools == other.player_pools
+    }
+}
+
+impl Eq for ResourceBank {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        game_parameters::AbstractParameters, game_state::AbstractGameState,
+    };
+
+    #[test]
+    fn test_pool_counts() {
+        let mut pool = TokenPool::new_with_id(0, 1);
+        pool.add("Gold", 3);
+        pool.add("Wood", 2);
+        pool.add("Stone", 0);
+        assert_eq!(pool.total(), 5);
+        assert_eq!(pool.get_size(), 0);
+        assert_eq!(pool.total_count(), 5);
+        assert_eq!(pool.to_string(), "{Gold: 3, Wood: 2}");
+
+        assert_eq!(
+            pool.remove("Gold", 4),
+            Err(TokenPoolError::NotEnoughTokens {
+                token_type: "Gold".to_string(),
+                available: 3,
+                requested: 4
+            })
+        );
+        assert!(pool.remove("Wood", 2).is_ok());
+        assert_eq!(pool.count("Wood"), 0);
+        assert_eq!(pool.counts().len(), 1);
+
+        // Paying a cost is all or nothing
+        assert!(pool.has_all(&[("Gold", 2), ("Gold", 1)]));
+        assert!(pool.remove_all(&[("Gold", 2), ("Wood", 1)]).is_err());
+        assert_eq!(pool.count("Gold"), 3);
+        assert!(pool.remove_all(&[("Gold", 2), ("Gold", 1)]).is_ok());
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn test_bank_transfers_conserve_tokens() {
+        let mut ids = IdAllocator::new();
+        let mut bank = ResourceBank::new(2, &mut ids);
+        bank.add_to_supply("Coin", 50);
+
+        assert!(bank.transfer(-1, 0, "Coin", 10).is_ok());
+        assert!(bank.transfer(0, 1, "Coin", 4).is_ok());
+        assert_eq!(bank.pool(0).unwrap().count("Coin"), 6);
+        assert_eq!(bank.pool(1).unwrap().count("Coin"), 4);
+        assert_eq!(bank.supply().count("Coin"), 40);
+        assert_eq!(bank.total("Coin"), 50);
+        assert_eq!(bank.get_size(), 3);
+        bank.add_to_supply("Gem", 5);
+        assert_eq!(bank.total_count(), 55);
+        assert!(bank.remove_from_supply("Gem", 5).is_ok());
+        assert_eq!(bank.get_components().len(), 3);
+        assert_eq!(bank.component_type(), ComponentType::ResourceBank);
+        assert_eq!(bank.supply().component_type(), ComponentType::TokenPool);
+
+        assert_eq!(
+            bank.transfer(1, 2, "Coin", 1),
+            Err(TokenPoolError::UnknownHolder(2))
+        );
+        assert!(bank.transfer(1, 0, "Coin", 5).is_err());
+        assert_eq!(bank.pool(1).unwrap().count("Coin"), 4);
+        assert!(bank.check_conservation().is_ok());
+
+        assert!(bank.remove_from_supply("Coin", 40).is_ok());
+        assert_eq!(bank.total("Coin"), 10);
+        assert!(bank.check_conservation().is_ok());
+
+        // Changes that bypass the bank are detected
+        bank.pool_mut(0).unwrap().add("Coin", 1);
+        bank.pool_mut(1).unwrap().add("Gem", 1);
+        assert_eq!(
+            bank.check_conservation(),
+            Err(TokenPoolError::ConservationViolated {
+                token_type: "Coin".to_string(),
+                expected: 10,
+                found: 11
+            })
+        );
+
+        // Tokens added to the supply behind the bank's back can still be removed
+        let mut bank = ResourceBank::new(1, &mut ids);
+        bank.add_to_supply("Gem", 2);
+        bank.pool_mut(-1).unwrap().add("Gem", 3);
+        assert!(bank.remove_from_supply("Gem", 5).is_ok());
+        assert!(bank.check_conservation().is_ok());
+    }
+
+    #[test]
+    fn test_rendering() {
+        let gs = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let mut bank = ResourceBank::new(2, &mut IdAllocator::new());
+        bank.add_to_supply("Coin", 10);
+        bank.add_to_supply("Gem", 2);
+        bank.transfer(-1, 1, "Coin", 3).unwrap();
+        bank.pool_mut(1)
+            .unwrap()
+            .set_visibility_mode(VisibilityMode::VisibleToOwner);
+
+        assert_eq!(bank.short_string(), "ResourceBank {Coin: 10, Gem: 2}");
+        assert_eq!(
+            bank.string_for_player(&gs, 0),
+            "Supply: {Coin: 7, Gem: 2}\nPlayer 0: {}\nPlayer 1: {? x 3}\n"
+        );
+        assert_eq!(
+            bank.string_for_player(&gs, 1),
+            "Supply: {Coin: 7, Gem: 2}\nPlayer 0: {}\nPlayer 1: {Coin: 3}\n"
+        );
+        bank.set_visibility_mode(VisibilityMode::HiddenToAll);
+        assert_eq!(
+            bank.string_for_player(&gs, 1),
+            "Supply: {? x 9}\nPlayer 0: {? x 0}\nPlayer 1: {? x 3}\n"
+        );
+        bank.set_visibility_mode(VisibilityMode::FirstVisibleToAll);
+        assert_eq!(
+            bank.string_for_player(&gs, 1),
+            "Supply: {Coin: 7, Gem: 2}\nPlayer 0: {? x 0}\nPlayer 1: {? x 3}\n"
+        );
+        assert_eq!(visibility::hidden_indices(&gs, &bank, 1), vec![1, 2]);
+
+        let mut pool = TokenPool::new_with_id(0, 1);
+        assert_eq!(pool.string_for_player(&gs, 1), "{}");
+        pool.add("Gold", 2);
+        pool.set_visibility_mode(VisibilityMode::VisibleToOwner);
+        assert_eq!(pool.string_for_player(&gs, 0), "{Gold: 2}");
+        assert_eq!(pool.string_for_player(&gs, 1), "{? x 2}");
+        assert!(visibility::hidden_indices(&gs, &pool, 1).is_empty());
+    }
+}
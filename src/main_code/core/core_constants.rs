@@ -1,25 +1,54 @@
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum ComponentType {
     Area,
+    Board,
+    BoardNode,
+    Card,
+    Counter,
+    Deck,
+    Dice,
+    DicePool,
+    ResourceBank,
     Token,
+    TokenPool,
 }
 
 impl std::fmt::Display for ComponentType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let name = match self {
             ComponentType::Area => "Area",
+            ComponentType::Board => "Board",
+            ComponentType::BoardNode => "BoardNode",
+            ComponentType::Card => "Card",
+            ComponentType::Counter => "Counter",
+            ComponentType::Deck => "Deck",
+            ComponentType::Dice => "Dice",
+            ComponentType::DicePool => "DicePool",
+            ComponentType::ResourceBank => "ResourceBank",
             ComponentType::Token => "Token",
+            ComponentType::TokenPool => "TokenPool",
         };
         write!(f, "{}", name)
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for ComponentType {
     fn default() -> Self {
         ComponentType::Token
     }
 }
 
+/// What happens to the components of a player eliminated mid-game (see
+/// ForwardModel::on_player_eliminated).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum EliminationPolicy {
+    /// The containers of the player (e.g. their hand) become visible to everyone
+    Reveal,
+    /// Every component of the player is given to the game (owner -1)
+    Discard,
+}
+
 /**
  * Used in Components that contain other Components (see ComponentContainer) to mark which players can see the
  * contents.
@@ -66,8 +95,17 @@ impl GameResult {
             GameResult::GameEnd => 3,
         }
     }
+
+    /// Returns true if the result takes the player out of the game while the rest may continue
+    pub fn is_elimination(&self) -> bool {
+        matches!(
+            self,
+            GameResult::LoseGame | GameResult::Disqualify | GameResult::Timeout
+        )
+    }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for GameResult {
     fn default() -> Self {
         GameResult::GameOngoing
@@ -1,7 +1,11 @@
 use crate::main_code::core::{
-    actions::action::Action, core_constants::GameResult, game_state::GameState,
+    actions::action::Action,
+    core_constants::{EliminationPolicy, GameResult, VisibilityMode},
+    game_state::GameState,
     glu::extended_glu::ExtendedGluTrait,
+    ownership,
 };
+use std::collections::BTreeMap;
 
 pub trait ForwardModel {
     /// Combines both the base and specific setup methods. Called from the game loop.
@@ -63,6 +67,128 @@ pub trait ForwardModel {
     /// - `action`: The action requested to be played by a player.
     fn next_impl(&self, current_state: &mut Box<dyn GameState>, action: Box<dyn Action>);
 
+    /// Passes the turn to the next player still in the game, skipping eliminated players.
+    ///
+    /// # Parameters
+    /// - `game_state`: The game state whose turn ends.
+    fn end_player_turn(&self, game_state: &mut Box<dyn GameState>) {
+        game_state.end_player_turn();
+    }
+
+    /// What happens to the components of an eliminated player. Reveal by default.
+    fn elimination_policy(&self) -> EliminationPolicy {
+        EliminationPolicy::Reveal
+    }
+
+    /// Called when a player is eliminated, so that the game can reveal or discard the hidden
+    /// components of the player according to its rules. By default applies the elimination
+    /// policy to the registered components owned by the player and to those it owns inside
+    /// registered containers: Reveal makes the registered containers of the player visible to
+    /// all, and Discard gives all of them to the game, recording it in the ownership history.
+    ///
+    /// # Parameters
+    /// - `game_state`: The game state, already updated with the elimination.
+    /// - `player`: The eliminated player.
+    fn on_player_eliminated(&self, game_state: &mut Box<dyn GameState>, player: usize) {
+        let owner = player as i32;
+        // (registered container, component) of every component owned by the player
+        let mut owned = vec![];
+        for id in game_state.registered_component_ids() {
+            let Some(component) = game_state.get_component(id) else {
+                continue;
+            };
+            if component.owner_id() == owner {
+                owned.push((id, id));
+            }
+            if let Some(container) = component.as_container() {
+                owned.extend(
+                    ownership::owned_by(container, owner)
+                        .iter()
+                        .map(|c| (id, c.component_id())),
+                );
+            }
+        }
+
+        for (container_id, component_id) in owned {
+            match self.elimination_policy() {
+                EliminationPolicy::Reveal if container_id == component_id => {
+                    if let Some(container) = game_state
+                        .get_component_mut(container_id)
+                        .and_then(|c| c.as_container_mut())
+                    {
+                        container.set_visibility_mode(VisibilityMode::VisibleToAll);
+                    }
+                }
+                EliminationPolicy::Reveal => {}
+                EliminationPolicy::Discard => {
+                    game_state.transfer_ownership_by_id(container_id, component_id, -1);
+                }
+            }
+        }
+    }
+
+    /// Eliminates a player from the game while the others continue. If the eliminated player
+    /// owned the turn, the turn passes to the next player still in the game. When only one team
+    /// (or player) remains, the game ends and that team wins.
+    ///
+    /// # Parameters
+    /// - `game_state`: The game state to modify.
+    /// - `player`: The player to eliminate.
+    fn eliminate_player(&self, game_state: &mut Box<dyn GameState>, player: usize) {
+        game_state.set_player_result(GameResult::LoseGame, player);
+        self.on_player_eliminated(game_state, player);
+
+        let active_teams = game_state.active_teams();
+        if active_teams.len() <= 1 {
+            game_state.set_game_status(GameResult::GameEnd);
+            if let Some(team) = active_teams.first() {
+                for p in game_state.team_members(*team) {
+                    if !game_state.is_player_eliminated(p) {
+                        game_state.set_player_result(GameResult::WinGame, p);
+                    }
+                }
+            }
+        } else if game_state.turn_owner() as usize == player {
+            self.end_player_turn(game_state);
+        }
+    }
+
+    /// Ends the game, setting the result of every team still in the game according to its team
+    /// score: the teams with the highest score win (or draw, if more than one team shares it) and
+    /// the rest lose. Eliminated players, and teams with no player left, keep their results.
+    ///
+    /// # Parameters
+    /// - `game_state`: The game state to finish.
+    fn end_game(&self, game_state: &mut Box<dyn GameState>) {
+        game_state.set_game_status(GameResult::GameEnd);
+
+        let active_teams = game_state.active_teams();
+        let best = active_teams
+            .iter()
+            .map(|t| game_state.team_score(*t))
+            .fold(f64::NEG_INFINITY, f64::max);
+        let leaders: Vec<usize> = active_teams
+            .iter()
+            .copied()
+            .filter(|t| game_state.team_score(*t) == best)
+            .collect();
+
+        for team in active_teams {
+            let result = if !leaders.contains(&team) {
+                GameResult::LoseGame
+            } else if leaders.len() > 1 {
+                GameResult::DrawGame
+            } else {
+                GameResult::WinGame
+            };
+            for p in game_state.team_members(team) {
+                if !game_state.is_player_eliminated(p) {
+                    game_state.set_player_result(result.clone(), p);
+                }
+            }
+        }
+    }
+
     fn compute_available_actions(
         &self,
         current_state: &mut Box<dyn GameState>,
@@ -76,8 +202,228 @@ pub trait ForwardModel {
         }
     }
 
+    #[allow(clippy::borrowed_box)]
     fn compute_available_actions_impl(
         &self,
         current_state: &Box<dyn GameState>,
     ) -> Vec<Box<dyn Action>>;
+
+    /// Returns the actions available to the given player in a decision taken by several players
+    /// at once. By default, the same actions as compute_available_actions_impl.
+    #[allow(clippy::borrowed_box)]
+    fn compute_player_actions_impl(
+        &self,
+        current_state: &Box<dyn GameState>,
+        _player: usize,
+    ) -> Vec<Box<dyn Action>> {
+        self.compute_available_actions_impl(current_state)
+    }
+
+    /// Collects the actions of every player still in the game for a decision taken by several
+    /// players at once (e.g. choosing a card simultaneously). Eliminated players are left out.
+    #[allow(clippy::borrowed_box)]
+    fn compute_simultaneous_actions(
+        &self,
+        current_state: &Box<dyn GameState>,
+    ) -> BTreeMap<usize, Vec<Box<dyn Action>>> {
+        current_state
+            .active_players()
+            .into_iter()
+            .map(|p| (p, self.compute_player_actions_impl(current_state, p)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        components::component::Component,
+        components::{card::Card, deck::Deck},
+        game_parameters::AbstractParameters,
+        game_state::AbstractGameState,
+    };
+
+    struct TestForwardModel {}
+
+    impl ForwardModel for TestForwardModel {
+        fn setup_impl(&self, _first_state: &mut Box<dyn GameState>) {}
+
+        fn next_impl(&self, _current_state: &mut Box<dyn GameState>, _action: Box<dyn Action>) {}
+
+        fn compute_available_actions_impl(
+            &self,
+            _current_state: &Box<dyn GameState>,
+        ) -> Vec<Box<dyn Action>> {
+            vec![]
+        }
+    }
+
+    struct DiscardingForwardModel {}
+
+    impl ForwardModel for DiscardingForwardModel {
+        fn setup_impl(&self, _first_state: &mut Box<dyn GameState>) {}
+
+        fn next_impl(&self, _current_state: &mut Box<dyn GameState>, _action: Box<dyn Action>) {}
+
+        fn compute_available_actions_impl(
+            &self,
+            _current_state: &Box<dyn GameState>,
+        ) -> Vec<Box<dyn Action>> {
+            vec![]
+        }
+
+        fn elimination_policy(&self) -> EliminationPolicy {
+            EliminationPolicy::Discard
+        }
+    }
+
+    fn new_state(n_players: u8) -> Box<dyn GameState> {
+        Box::new(AbstractGameState::new(
+            Box::new(AbstractParameters::new()),
+            n_players,
+            0,
+        ))
+    }
+
+    fn team_state(scores: [f64; 4]) -> Box<dyn GameState> {
+        let mut state = new_state(4);
+        state.set_teams(vec![0, 1, 1, 0]);
+        for (p, score) in scores.iter().enumerate() {
+            state.set_game_score(p, *score);
+        }
+        state
+    }
+
+    #[test]
+    fn test_end_game_with_teams() {
+        let mut state = team_state([1.0, 2.0, 2.0, 1.0]);
+        TestForwardModel {}.end_game(&mut state);
+        assert_eq!(state.game_status(), &GameResult::GameEnd);
+        assert_eq!(
+            state.player_results(),
+            &vec![
+                GameResult::LoseGame,
+                GameResult::WinGame,
+                GameResult::WinGame,
+                GameResult::LoseGame
+            ]
+        );
+
+        let mut state = team_state([1.0, 0.0, 2.0, 1.0]);
+        TestForwardModel {}.end_game(&mut state);
+        assert!(state
+            .player_results()
+            .iter()
+            .all(|r| *r == GameResult::DrawGame));
+    }
+
+    #[test]
+    fn test_end_game_ignores_eliminated_teams() {
+        let mut state = new_state(4);
+        state.set_teams(vec![0, 1, 1, 2]);
+        for (p, score) in [1.0, 5.0, 5.0, 3.0].iter().enumerate() {
+            state.set_game_score(p, *score);
+        }
+        state.set_player_result(GameResult::Disqualify, 1);
+        state.set_player_result(GameResult::Timeout, 2);
+
+        TestForwardModel {}.end_game(&mut state);
+        assert_eq!(
+            state.player_results(),
+            &vec![
+                GameResult::LoseGame,
+                GameResult::Disqualify,
+                GameResult::Timeout,
+                GameResult::WinGame
+            ]
+        );
+    }
+
+    #[test]
+    fn test_elimination_skips_turns_and_ends_game() {
+        let fm = TestForwardModel {};
+        let mut state = new_state(3);
+        fm.end_player_turn(&mut state);
+        assert_eq!(state.turn_owner(), 1);
+
+        fm.eliminate_player(&mut state, 1);
+        assert_eq!(state.turn_owner(), 2);
+        assert_eq!(state.game_status(), &GameResult::GameOngoing);
+        fm.end_player_turn(&mut state);
+        assert_eq!(state.turn_owner(), 0);
+
+        fm.eliminate_player(&mut state, 0);
+        assert_eq!(state.game_status(), &GameResult::GameEnd);
+        assert_eq!(
+            state.player_results(),
+            &vec![
+                GameResult::LoseGame,
+                GameResult::LoseGame,
+                GameResult::WinGame
+            ]
+        );
+    }
+
+    #[test]
+    fn test_elimination_with_teams() {
+        let fm = TestForwardModel {};
+        let mut state = team_state([0.0; 4]);
+        fm.eliminate_player(&mut state, 1);
+        assert_eq!(state.game_status(), &GameResult::GameOngoing);
+        fm.eliminate_player(&mut state, 2);
+        assert_eq!(state.game_status(), &GameResult::GameEnd);
+        assert_eq!(state.player_results()[0], GameResult::WinGame);
+        assert_eq!(state.player_results()[3], GameResult::WinGame);
+    }
+
+    // Player 1 holds a hand, and a card in the market deck owned by the game
+    fn state_with_hand() -> Box<dyn GameState> {
+        let mut state = new_state(3);
+        let mut hand = Deck::new_with_id("Hand", 1, 10, VisibilityMode::VisibleToOwner);
+        hand.add_to_bottom(Card::new_with_id("Card", 1));
+        state.add_component(Box::new(hand));
+        let mut market = Deck::new_with_id("Market", -1, 20, VisibilityMode::HiddenToAll);
+        let mut card = Card::new_with_id("Card", 2);
+        card.set_owner_id(1);
+        market.add_to_bottom(card);
+        market.add_to_bottom(Card::new_with_id("Card", 3));
+        state.add_component(Box::new(market));
+        state
+    }
+
+    #[test]
+    fn test_elimination_reveals_components() {
+        let mut state = state_with_hand();
+        TestForwardModel {}.eliminate_player(&mut state, 1);
+        let hand = state.get_component(10).unwrap().as_container().unwrap();
+        assert_eq!(hand.get_visibility_mode(), VisibilityMode::VisibleToAll);
+        let market = state.get_component(20).unwrap().as_container().unwrap();
+        assert_eq!(market.get_visibility_mode(), VisibilityMode::HiddenToAll);
+        assert!(state.ownership_history().is_empty());
+    }
+
+    #[test]
+    fn test_elimination_discards_components() {
+        let mut state = state_with_hand();
+        DiscardingForwardModel {}.eliminate_player(&mut state, 1);
+        let owners = |id: usize| -> Vec<i32> {
+            let container = state.get_component(id).unwrap().as_container().unwrap();
+            let mut owners = vec![container.owner_id()];
+            owners.extend(container.get_components().iter().map(|c| c.owner_id()));
+            owners
+        };
+        assert_eq!(owners(10), vec![-1, -1]);
+        assert_eq!(owners(20), vec![-1, -1, -1]);
+        assert_eq!(state.ownership_history().len(), 2);
+    }
+
+    #[test]
+    fn test_simultaneous_actions_skip_eliminated_players() {
+        let fm = TestForwardModel {};
+        let mut state = new_state(3);
+        fm.eliminate_player(&mut state, 1);
+        let actions = fm.compute_simultaneous_actions(&state);
+        assert_eq!(actions.keys().copied().collect::<Vec<_>>(), vec![0, 2]);
+    }
 }
@@ -1,9 +1,117 @@
-pub struct Game {}
+use crate::main_code::core::{
+    game_parameters::{AbstractParameters, GameParameters},
+    game_state::GameState,
+    player::Player,
+    seeds::GameSeeds,
+};
+
+pub struct Game {
+    game_parameters: Box<dyn GameParameters>,
+    players: Vec<Box<dyn Player>>,
+    // Seeds of this game, all derived from the game seed
+    seeds: GameSeeds,
+    // If true, the game parameters are sampled from their possible values at the start of the game
+    randomize_parameters: bool,
+    // Number of redetermination seeds already written to the log
+    logged_redeterminations: u64,
+    game_log: Vec<String>,
+}
 
 impl Game {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Game {
-        Game {}
+        Self::new_with_parameters(Box::new(AbstractParameters::default()))
+    }
+
+    pub fn new_with_parameters(game_parameters: Box<dyn GameParameters>) -> Game {
+        Game {
+            game_parameters,
+            players: vec![],
+            seeds: GameSeeds::new(0),
+            randomize_parameters: false,
+            logged_redeterminations: 0,
+            game_log: vec![],
+        }
+    }
+
+    pub fn game_parameters(&self) -> &dyn GameParameters {
+        self.game_parameters.as_ref()
+    }
+
+    pub fn players(&self) -> &Vec<Box<dyn Player>> {
+        &self.players
+    }
+
+    pub fn set_players(&mut self, players: Vec<Box<dyn Player>>) {
+        self.players = players;
+    }
+
+    pub fn seeds(&self) -> GameSeeds {
+        self.seeds
+    }
+
+    /// Sets the game seed, from which every other seed of the game is derived. When running many
+    /// games, use SeedTree::game_seed to obtain it from a master seed.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seeds = GameSeeds::new(seed);
+    }
+
+    pub fn set_randomize_parameters(&mut self, randomize_parameters: bool) {
+        self.randomize_parameters = randomize_parameters;
+    }
+
+    pub fn game_log(&self) -> &Vec<String> {
+        &self.game_log
+    }
+
+    pub fn log_event(&mut self, event: String) {
+        self.game_log.push(event);
+    }
+
+    /// Prepares the game to be played: stores the game seed in the parameters, seeds the players,
+    /// randomizes the game parameters if requested, and logs every seed used so that the game can
+    /// be reproduced.
+    pub fn reset(&mut self) {
+        self.game_log.clear();
+        self.logged_redeterminations = 0;
+        let seeds = self.seeds;
+        self.log_event(format!("Game seed: {}", seeds.game_seed()));
+        self.game_parameters.set_random_seed(seeds.game_seed());
+
+        if self.randomize_parameters {
+            let event = format!("Parameters seed: {}", seeds.parameters_seed());
+            self.log_event(event);
+            self.game_parameters.randomize(&mut seeds.parameters_rnd());
+            let event = format!("Randomized parameters: {}", self.game_parameters.to_json());
+            self.log_event(event);
+        }
+
+        for i in 0..self.players.len() {
+            self.players[i].set_seed(seeds.player_seed(i));
+            self.log_event(format!("Player {} seed: {}", i, seeds.player_seed(i)));
+        }
+
+        let event = format!(
+            "Redetermination root seed: {}",
+            seeds.redetermination_root_seed()
+        );
+        self.log_event(event);
     }
+
+    /// Logs the seed of every redetermination the game state has done since the last call, so
+    /// that each one can be replayed on its own.
+    pub fn log_redeterminations(&mut self, gs: &dyn GameState) {
+        let seeds = gs.redetermination_seeds();
+        for (i, seed) in seeds
+            .iter()
+            .enumerate()
+            .skip(self.logged_redeterminations as usize)
+        {
+            self.log_event(format!("Redetermination {} seed: {}", i, seed));
+        }
+        self.logged_redeterminations = seeds.len() as u64;
+    }
+
     /*
        pub fn run_one(game_to_play: GameType, players: Vec<u8>, seed: u64) -> Game {
            let game = Self::new();
@@ -14,7 +122,148 @@ impl Game {
            public static Game runOne(GameType gameToPlay, String parameterConfigFile, List<AbstractPlayer> players, long seed,
            boolean randomizeParameters, List<IGameListener> listeners, ActionController ac, int turnPause) {
     */
-    pub fn main(&self) {
+    pub fn main(&mut self) {
+        self.reset();
+        for event in &self.game_log {
+            println!("{}", event);
+        }
         println!("Ejecutando el juego...");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::tunable_parameters::{
+        ParameterError, ParameterSpace, ParameterType, ParameterValue, TunableParameter,
+    };
+
+    #[derive(Clone)]
+    struct TestParameters {
+        n_cards: i64,
+    }
+
+    impl GameParameters for TestParameters {
+        fn parameter_space(&self) -> ParameterSpace {
+            let mut space = ParameterSpace::new();
+            space.add(TunableParameter::new_with_values(
+                "n_cards",
+                10.into(),
+                (1..=100).map(|v| v.into()).collect(),
+            ));
+            space
+        }
+
+        fn get_parameter_value(&self, name: &str) -> Option<ParameterValue> {
+            (name == "n_cards").then(|| self.n_cards.into())
+        }
+
+        fn set_parameter_value_impl(
+            &mut self,
+            name: &str,
+            value: ParameterValue,
+        ) -> Result<(), ParameterError> {
+            match (name, value.as_int()) {
+                ("n_cards", Some(v)) => {
+                    self.n_cards = v;
+                    Ok(())
+                }
+                ("n_cards", None) => Err(ParameterError::TypeMismatch {
+                    name: name.to_string(),
+                    expected: ParameterType::Int,
+                    found: value.parameter_type(),
+                }),
+                _ => Err(ParameterError::UnknownParameter(name.to_string())),
+            }
+        }
+    }
+
+    fn randomized_game(seed: u64) -> Game {
+        let mut game = Game::new_with_parameters(Box::new(TestParameters { n_cards: 10 }));
+        game.set_seed(seed);
+        game.set_randomize_parameters(true);
+        game.reset();
+        game
+    }
+
+    #[test]
+    fn test_randomize_parameters() {
+        let g1 = randomized_game(3);
+        let g2 = randomized_game(3);
+        assert_eq!(g1.game_log().len(), 4);
+        assert_eq!(g1.game_log(), g2.game_log());
+        assert_eq!(
+            g1.game_parameters().get_parameter_value("n_cards"),
+            g2.game_parameters().get_parameter_value("n_cards")
+        );
+
+        let mut game = Game::new_with_parameters(Box::new(TestParameters { n_cards: 10 }));
+        game.reset();
+        assert!(!game.game_log()[1].starts_with("Randomized parameters"));
+        assert_eq!(
+            game.game_parameters().get_parameter_value("n_cards"),
+            Some(10.into())
+        );
+    }
+
+    #[test]
+    fn test_players_are_seeded_from_game_seed() {
+        use crate::main_code::{
+            core::actions::{action::Action, do_nothing::DoNothing},
+            core::game_state::AbstractGameState,
+            core::id_allocator::IdAllocator,
+            core::seeds::SeedTree,
+            players::random_player::RandomPlayer,
+        };
+
+        let mut ids = IdAllocator::new();
+        let actions: Vec<Box<dyn Action>> = (0..100)
+            .map(|_| Box::new(DoNothing::new(&mut ids)) as Box<dyn Action>)
+            .collect();
+        let state = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let play = |seed: u64| {
+            let mut game = Game::new();
+            game.set_seed(seed);
+            game.set_players(vec![
+                Box::new(RandomPlayer::new()),
+                Box::new(RandomPlayer::new()),
+            ]);
+            game.reset();
+            let mut players = game.players().clone();
+            let chosen: Vec<i32> = (0..10)
+                .map(|_| players[1].next_action(&state, &actions).id())
+                .collect();
+            (game.game_log().clone(), chosen)
+        };
+
+        let seed = SeedTree::new(99).game_seed(1234);
+        assert_eq!(play(seed), play(seed));
+        assert_ne!(play(seed).1, play(seed + 1).1);
+    }
+
+    #[test]
+    fn test_seeds_are_logged_and_propagated() {
+        use crate::main_code::core::game_state::{AbstractGameState, GameState};
+
+        let mut game = Game::new();
+        game.set_seed(17);
+        game.reset();
+        assert_eq!(game.game_parameters().random_seed(), Some(17));
+
+        let mut state = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 17);
+        state.redetermination_rnd();
+        game.log_redeterminations(&state);
+        state.redetermination_rnd();
+        game.log_redeterminations(&state);
+
+        let seeds = game.seeds();
+        let log = game.game_log();
+        assert_eq!(
+            log[log.len() - 2..],
+            [
+                format!("Redetermination 0 seed: {}", seeds.redetermination_seed(0)),
+                format!("Redetermination 1 seed: {}", seeds.redetermination_seed(1)),
+            ]
+        );
+    }
+}
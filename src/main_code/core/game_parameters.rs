@@ -1,10 +1,220 @@
+use crate::main_code::{
+    core::tunable_parameters::{
+        ParameterAssignment, ParameterError, ParameterSpace, ParameterType, ParameterValue,
+        TunableParameter,
+    },
+    utilities::json_utils::{load_json_file, save_json_file, JsonError},
+};
+use rand::RngCore;
+use serde_json::{Map, Value};
 use std::{
+    fmt,
     hash::{Hash, Hasher},
+    path::Path,
     time::{SystemTime, UNIX_EPOCH},
 };
 
-pub trait GameParameters {}
+/// Errors produced when loading parameters from a JSON config.
+#[derive(Debug)]
+pub enum ConfigError {
+    Json(JsonError),
+    NotAnObject,
+    Parameters(Vec<ParameterError>),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Json(e) => write!(f, "{}", e),
+            ConfigError::NotAnObject => write!(f, "parameter config must be a JSON object"),
+            ConfigError::Parameters(errors) => {
+                let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+                write!(f, "invalid parameter config: {}", errors.join("; "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<JsonError> for ConfigError {
+    fn from(e: JsonError) -> Self {
+        ConfigError::Json(e)
+    }
+}
+
+/// Key under which the random seed is stored in JSON configs. The seed is not a tunable parameter,
+/// so it is never part of a search space or randomized.
+pub const RANDOM_SEED_KEY: &str = "random_seed";
+
+/// Parameters of a game (or agent) exposed as a set of named, tunable parameters. Game-specific
+/// parameter structs usually embed AbstractParameters, extend its parameter space with their own
+/// declarations and delegate the names they don't know to it; JSON configs, randomization and
+/// search spaces then cover both.
+pub trait GameParameters: dyn_clone::DynClone + downcast_rs::Downcast {
+    /// Returns the declaration of every tunable parameter, with its default and allowed values.
+    fn parameter_space(&self) -> ParameterSpace;
+
+    /// Returns the current value of the named parameter, or None if it does not exist.
+    fn get_parameter_value(&self, name: &str) -> Option<ParameterValue>;
+
+    /// Writes a value into the named parameter. Returns an error, leaving the parameters
+    /// unchanged, if the name is unknown or the value does not fit the field it is stored in.
+    fn set_parameter_value_impl(
+        &mut self,
+        name: &str,
+        value: ParameterValue,
+    ) -> Result<(), ParameterError>;
+
+    /// Returns the seed of the games played with these parameters, if they keep one.
+    fn random_seed(&self) -> Option<u64> {
+        None
+    }
+
+    /// Sets the seed of the games played with these parameters. Returns false if they don't
+    /// keep one.
+    fn set_random_seed(&mut self, _random_seed: u64) -> bool {
+        false
+    }
+
+    /// Validates the value against the parameter space and writes it into the named parameter.
+    fn set_parameter_value(
+        &mut self,
+        name: &str,
+        value: ParameterValue,
+    ) -> Result<(), ParameterError> {
+        self.parameter_space().validate(name, &value)?;
+        self.set_parameter_value_impl(name, value)
+    }
+
+    /// Returns the names of all tunable parameters, in declaration order.
+    fn parameter_names(&self) -> Vec<String> {
+        self.parameter_space().names()
+    }
+
+    /// Returns the current value of every tunable parameter.
+    fn parameter_values(&self) -> ParameterAssignment {
+        self.parameter_names()
+            .into_iter()
+            .filter_map(|name| self.get_parameter_value(&name).map(|v| (name, v)))
+            .collect()
+    }
+
+    /// Returns the current value of every tunable parameter, and the random seed if kept, as a
+    /// JSON object.
+    fn to_json(&self) -> Value {
+        let mut map: Map<String, Value> = self
+            .parameter_values()
+            .into_iter()
+            .map(|(name, value)| (name, value.to_json()))
+            .collect();
+        if let Some(seed) = self.random_seed() {
+            map.insert(RANDOM_SEED_KEY.to_string(), seed.into());
+        }
+        Value::Object(map)
+    }
 
+    /// Sets the parameters present in a JSON object. Every unknown or ill-typed key is reported,
+    /// and no parameter is modified unless the whole object is valid.
+    fn load_json(&mut self, json: &Value) -> Result<(), ConfigError> {
+        let object = json.as_object().ok_or(ConfigError::NotAnObject)?;
+        let space = self.parameter_space();
+
+        let mut values = vec![];
+        let mut seed = None;
+        let mut errors = vec![];
+        for (name, value) in object {
+            if name == RANDOM_SEED_KEY && self.random_seed().is_some() {
+                match value.as_u64() {
+                    Some(v) => seed = Some(v),
+                    None => errors.push(ParameterError::InvalidJson {
+                        name: name.clone(),
+                        expected: ParameterType::Int,
+                        value: value.clone(),
+                    }),
+                }
+                continue;
+            }
+            match space.parse_json(name, value) {
+                Ok(v) => values.push((name, v)),
+                Err(e) => errors.push(e),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(ConfigError::Parameters(errors));
+        }
+
+        let previous = self.parameter_values();
+        for (name, value) in values {
+            if let Err(e) = self.set_parameter_value_impl(name, value) {
+                // Current values were accepted before, so restoring them cannot fail
+                for (name, value) in previous {
+                    let _ = self.set_parameter_value_impl(&name, value);
+                }
+                return Err(ConfigError::Parameters(vec![e]));
+            }
+        }
+        if let Some(seed) = seed {
+            self.set_random_seed(seed);
+        }
+        Ok(())
+    }
+
+    /// Sets the parameters present in a JSON config file (see load_json).
+    fn load_json_file(&mut self, path: &Path) -> Result<(), ConfigError> {
+        let json = load_json_file(path)?;
+        self.load_json(&json)
+    }
+
+    /// Writes the current value of every tunable parameter to a JSON config file.
+    fn save_json_file(&self, path: &Path) -> Result<(), JsonError> {
+        save_json_file(path, &self.to_json())
+    }
+
+    /// Sets every tunable parameter with declared possible values to a value sampled from them,
+    /// and returns the applied assignment. Parameters without possible values keep their value,
+    /// as do those whose sampled value is rejected by set_parameter_value_impl.
+    fn randomize(&mut self, rnd: &mut dyn RngCore) -> ParameterAssignment {
+        self.parameter_space()
+            .random_assignment(rnd)
+            .into_iter()
+            .filter(|(name, value)| self.set_parameter_value_impl(name, value.clone()).is_ok())
+            .collect()
+    }
+
+    /// Sets every tunable parameter back to its default value. Stops at the first default
+    /// rejected by set_parameter_value_impl.
+    fn reset_parameters(&mut self) -> Result<(), ParameterError> {
+        for (name, value) in self.parameter_space().default_assignment() {
+            self.set_parameter_value_impl(&name, value)?;
+        }
+        Ok(())
+    }
+}
+dyn_clone::clone_trait_object!(GameParameters);
+downcast_rs::impl_downcast!(GameParameters);
+
+impl dyn GameParameters {
+    /// Returns a copy of these parameters with the given assignment applied. Parameters not
+    /// present in the assignment keep their current value.
+    pub fn instantiate(
+        &self,
+        assignment: &ParameterAssignment,
+    ) -> Result<Box<dyn GameParameters>, ParameterError> {
+        let mut copy = dyn_clone::clone_box(self);
+        for (name, value) in assignment {
+            copy.set_parameter_value(name, value.clone())?;
+        }
+        Ok(copy)
+    }
+}
+
+/// Largest round limit accepted by max_rounds and timeout_rounds.
+pub const MAX_ROUNDS_LIMIT: i64 = 10_000;
+
+/// Parameters common to every game. Cloning keeps the random seed, so a copy reproduces the
+/// same game; use set_random_seed to give it a different one.
+#[derive(Debug, Clone)]
 pub struct AbstractParameters {
     random_seed: u64,
     max_rounds: i32,
@@ -28,9 +238,6 @@ impl AbstractParameters {
             .as_secs()
     }
 
-    pub fn random_seed(&self) -> u64 {
-        self.random_seed
-    }
     pub fn max_rounds(&self) -> i32 {
         self.max_rounds
     }
@@ -69,25 +276,98 @@ impl Default for AbstractParameters {
     }
 }
 
-impl GameParameters for AbstractParameters {}
+impl GameParameters for AbstractParameters {
+    fn parameter_space(&self) -> ParameterSpace {
+        let defaults = AbstractParameters::default();
+        let mut space = ParameterSpace::new();
+        // -1 means no limit
+        space.add(
+            TunableParameter::new("max_rounds", defaults.max_rounds.into())
+                .with_range(-1, MAX_ROUNDS_LIMIT),
+        );
+        space.add(
+            TunableParameter::new("timeout_rounds", defaults.timeout_rounds.into())
+                .with_range(-1, MAX_ROUNDS_LIMIT),
+        );
+        space.add(TunableParameter::new(
+            "thinking_time_mins",
+            defaults.thinking_time_mins.into(),
+        ));
+        space.add(TunableParameter::new(
+            "increment_action_s",
+            defaults.increment_action_s.into(),
+        ));
+        space.add(TunableParameter::new(
+            "increment_turn_s",
+            defaults.increment_turn_s.into(),
+        ));
+        space.add(TunableParameter::new(
+            "increment_round_s",
+            defaults.increment_round_s.into(),
+        ));
+        space.add(TunableParameter::new(
+            "increment_milestone_s",
+            defaults.increment_milestone_s.into(),
+        ));
+        space
+    }
 
-/**
- * Clone this game parameter object.
- *
- * @return - new object with the same parameters, but a new random seed.
- */
-impl Clone for AbstractParameters {
-    fn clone(&self) -> Self {
-        Self {
-            random_seed: Self::generate_random_seed(),
-            max_rounds: self.max_rounds,
-            timeout_rounds: self.timeout_rounds,
-            thinking_time_mins: self.thinking_time_mins,
-            increment_action_s: self.increment_action_s,
-            increment_turn_s: self.increment_turn_s,
-            increment_round_s: self.increment_round_s,
-            increment_milestone_s: self.increment_milestone_s,
+    fn get_parameter_value(&self, name: &str) -> Option<ParameterValue> {
+        match name {
+            "max_rounds" => Some(self.max_rounds.into()),
+            "timeout_rounds" => Some(self.timeout_rounds.into()),
+            "thinking_time_mins" => Some(self.thinking_time_mins.into()),
+            "increment_action_s" => Some(self.increment_action_s.into()),
+            "increment_turn_s" => Some(self.increment_turn_s.into()),
+            "increment_round_s" => Some(self.increment_round_s.into()),
+            "increment_milestone_s" => Some(self.increment_milestone_s.into()),
+            _ => None,
+        }
+    }
+
+    fn set_parameter_value_impl(
+        &mut self,
+        name: &str,
+        value: ParameterValue,
+    ) -> Result<(), ParameterError> {
+        let Some(int_value) = value.as_int() else {
+            return match self.parameter_space().get(name) {
+                Some(parameter) => Err(ParameterError::TypeMismatch {
+                    name: name.to_string(),
+                    expected: parameter.parameter_type(),
+                    found: value.parameter_type(),
+                }),
+                None => Err(ParameterError::UnknownParameter(name.to_string())),
+            };
+        };
+        let as_i32 = |v: i64| {
+            i32::try_from(v).map_err(|_| ParameterError::OutOfRange {
+                name: name.to_string(),
+                value: v,
+                min: i32::MIN.into(),
+                max: i32::MAX.into(),
+            })
+        };
+        match name {
+            "max_rounds" => self.max_rounds = as_i32(int_value)?,
+            "timeout_rounds" => self.timeout_rounds = as_i32(int_value)?,
+            "thinking_time_mins" => self.thinking_time_mins = int_value,
+            "increment_action_s" => self.increment_action_s = int_value,
+            "increment_turn_s" => self.increment_turn_s = int_value,
+            "increment_round_s" => self.increment_round_s = int_value,
+            "increment_milestone_s" => self.increment_milestone_s = int_value,
+            _ => return Err(ParameterError::UnknownParameter(name.to_string())),
         }
+        Ok(())
+    }
+
+    fn random_seed(&self) -> Option<u64> {
+        Some(self.random_seed)
+    }
+
+    fn set_random_seed(&mut self, random_seed: u64) -> bool {
+        self.random_seed = random_seed;
+        true
     }
 }
 
@@ -116,3 +396,215 @@ impl Hash for AbstractParameters {
         self.increment_milestone_s.hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_and_set_by_name() {
+        let mut params = AbstractParameters::new();
+        assert_eq!(params.parameter_names().len(), 7);
+        assert_eq!(params.get_parameter_value("max_rounds"), Some((-1).into()));
+
+        params.set_parameter_value("max_rounds", 50.into()).unwrap();
+        assert_eq!(params.max_rounds(), 50);
+        assert!(params
+            .set_parameter_value("max_rounds", true.into())
+            .is_err());
+        assert!(params.set_parameter_value("unknown", 1.into()).is_err());
+
+        params.reset_parameters().unwrap();
+        assert_eq!(params, AbstractParameters::default());
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let params: Box<dyn GameParameters> = Box::new(AbstractParameters::new());
+        let mut assignment = ParameterAssignment::new();
+        assignment.insert("thinking_time_mins".to_string(), 5.into());
+
+        let copy = params.instantiate(&assignment).unwrap();
+        let copy = copy.downcast_ref::<AbstractParameters>().unwrap();
+        assert_eq!(copy.thinking_time_mins(), 5);
+        assert_eq!(
+            params.get_parameter_value("thinking_time_mins"),
+            Some(90.into())
+        );
+    }
+
+    #[test]
+    fn test_clone_keeps_seed() {
+        let mut params = AbstractParameters::new();
+        assert!(params.set_random_seed(42));
+        assert_eq!(params.clone().random_seed(), Some(42));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let mut params = AbstractParameters::new();
+        params.set_parameter_value("max_rounds", 20.into()).unwrap();
+        params.set_random_seed(u64::MAX);
+
+        let mut loaded = AbstractParameters::new();
+        loaded.load_json(&params.to_json()).unwrap();
+        assert_eq!(loaded, params);
+        assert_eq!(loaded.random_seed(), Some(u64::MAX));
+
+        let json = serde_json::json!({ "max_rounds": 5, "random_seed": -1 });
+        assert!(loaded.load_json(&json).is_err());
+        assert_eq!(loaded.max_rounds(), 20);
+    }
+
+    #[test]
+    fn test_set_parameter_value_impl_reports_errors() {
+        let mut params = AbstractParameters::new();
+        assert_eq!(
+            params.set_parameter_value_impl("unknown", 1.into()),
+            Err(ParameterError::UnknownParameter("unknown".to_string()))
+        );
+        assert!(matches!(
+            params.set_parameter_value_impl("max_rounds", "ten".into()),
+            Err(ParameterError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            params.set_parameter_value_impl("timeout_rounds", (1_i64 << 40).into()),
+            Err(ParameterError::OutOfRange { .. })
+        ));
+        assert_eq!(params, AbstractParameters::default());
+    }
+
+    #[derive(Debug, Clone)]
+    struct CardGameParameters {
+        base: AbstractParameters,
+        hand_size: i64,
+    }
+
+    impl GameParameters for CardGameParameters {
+        fn parameter_space(&self) -> ParameterSpace {
+            let mut space = self.base.parameter_space();
+            space.add(TunableParameter::new_with_values(
+                "hand_size",
+                5.into(),
+                vec![3.into(), 5.into(), 7.into()],
+            ));
+            space
+        }
+
+        fn get_parameter_value(&self, name: &str) -> Option<ParameterValue> {
+            match name {
+                "hand_size" => Some(self.hand_size.into()),
+                _ => self.base.get_parameter_value(name),
+            }
+        }
+
+        fn set_parameter_value_impl(
+            &mut self,
+            name: &str,
+            value: ParameterValue,
+        ) -> Result<(), ParameterError> {
+            if name != "hand_size" {
+                return self.base.set_parameter_value_impl(name, value);
+            }
+            self.hand_size = value.as_int().ok_or(ParameterError::TypeMismatch {
+                name: name.to_string(),
+                expected: ParameterType::Int,
+                found: value.parameter_type(),
+            })?;
+            Ok(())
+        }
+
+        fn random_seed(&self) -> Option<u64> {
+            self.base.random_seed()
+        }
+
+        fn set_random_seed(&mut self, random_seed: u64) -> bool {
+            self.base.set_random_seed(random_seed)
+        }
+    }
+
+    #[test]
+    fn test_game_specific_parameters_json() {
+        let mut params = CardGameParameters {
+            base: AbstractParameters::new(),
+            hand_size: 5,
+        };
+        let json = serde_json::json!({ "hand_size": 7, "max_rounds": 12, "random_seed": 9 });
+        params.load_json(&json).unwrap();
+        assert_eq!(params.hand_size, 7);
+        assert_eq!(params.base.max_rounds(), 12);
+        assert_eq!(params.to_json()["random_seed"], 9);
+
+        let mut loaded = CardGameParameters {
+            base: AbstractParameters::new(),
+            hand_size: 5,
+        };
+        loaded.load_json(&params.to_json()).unwrap();
+        assert_eq!(loaded.to_json(), params.to_json());
+        assert!(loaded
+            .load_json(&serde_json::json!({ "hand_size": 4 }))
+            .is_err());
+    }
+
+    #[test]
+    fn test_load_json_reports_every_error() {
+        let mut params = AbstractParameters::new();
+        let json = serde_json::json!({
+            "max_rounds": 10,
+            "thinking_time": 5,
+            "timeout_rounds": "ten",
+        });
+
+        match params.load_json(&json) {
+            Err(ConfigError::Parameters(errors)) => {
+                assert_eq!(errors.len(), 2);
+                assert!(errors.contains(&ParameterError::UnknownParameter(
+                    "thinking_time".to_string()
+                )));
+            }
+            _ => panic!("Expected parameter errors"),
+        }
+        assert_eq!(params.max_rounds(), -1);
+    }
+
+    #[test]
+    fn test_randomize_keeps_loaded_values() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut params = AbstractParameters::new();
+        params
+            .load_json(&serde_json::json!({ "thinking_time_mins": 30 }))
+            .unwrap();
+        let assignment = params.randomize(&mut StdRng::seed_from_u64(1));
+        assert_eq!(assignment.len(), 2);
+        assert_eq!(
+            assignment.get("max_rounds"),
+            Some(&params.max_rounds().into())
+        );
+        assert!((-1..=MAX_ROUNDS_LIMIT).contains(&params.timeout_rounds().into()));
+        assert_eq!(params.thinking_time_mins(), 30);
+    }
+
+    #[test]
+    fn test_load_json_rejects_out_of_range_rounds() {
+        let mut params = AbstractParameters::new();
+        let json = serde_json::json!({ "max_rounds": 1_i64 << 32 });
+
+        match params.load_json(&json) {
+            Err(ConfigError::Parameters(errors)) => assert_eq!(
+                errors,
+                vec![ParameterError::OutOfRange {
+                    name: "max_rounds".to_string(),
+                    value: 1 << 32,
+                    min: -1,
+                    max: MAX_ROUNDS_LIMIT,
+                }]
+            ),
+            _ => panic!("Expected an out of range error"),
+        }
+        assert_eq!(params.max_rounds(), -1);
+        assert!(params
+            .set_parameter_value("timeout_rounds", (-(1_i64 << 40)).into())
+            .is_err());
+    }
+}
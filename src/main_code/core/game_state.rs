@@ -1,10 +1,11 @@
 use crate::main_code::core::{
-    core_constants::GameResult, game_parameters::GameParameters, glu::extended_glu::ExtendedGLU,
-    interfaces::action_type::ActionType,
+    components::component::Component, core_constants::GameResult, game_parameters::GameParameters,
+    glu::extended_glu::ExtendedGLU, id_allocator::IdAllocator, interfaces::action_type::ActionType,
+    ownership::OwnershipChange, seeds::GameSeeds,
 };
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 
-use rand::rngs::ThreadRng;
+use rand::rngs::StdRng;
 
 pub trait GameState {
     /// Determines the current player by checking if there are actions in progress
@@ -16,11 +17,48 @@ pub trait GameState {
     /// Returns the current turn owner (in case no actions are in progress)
     fn turn_owner(&self) -> i8;
 
+    fn set_turn_owner(&mut self, turn_owner: i8);
+
     /// Returns the number of players in the game
     fn n_players(&self) -> u8;
 
-    /// Returns random source
-    fn rnd(&self) -> ThreadRng;
+    /// Returns the main random source of the game, seeded from the game seed
+    fn rnd(&mut self) -> &mut StdRng;
+
+    /// Returns the seeds this game was created with
+    fn seeds(&self) -> GameSeeds;
+
+    /// Returns a fresh random source for the next redetermination, seeded from its own branch of
+    /// the seed tree so that it doesn't affect the main random source
+    fn redetermination_rnd(&mut self) -> StdRng;
+
+    /// Returns the number of redeterminations done so far
+    fn redetermination_count(&self) -> u64;
+
+    /// Returns the seeds of the redeterminations done so far, in the order they were used
+    fn redetermination_seeds(&self) -> Vec<u64> {
+        let seeds = self.seeds();
+        (0..self.redetermination_count())
+            .map(|i| seeds.redetermination_seed(i))
+            .collect()
+    }
+
+    /// Allocator of the IDs of the components created in this game
+    fn component_ids(&mut self) -> &mut IdAllocator;
+
+    /// Reserves the ID of a new component
+    fn next_component_id(&mut self) -> usize {
+        self.component_ids().next_id()
+    }
+
+    /// Allocator of the IDs of the actions and GLUs created in this game, which are recorded in
+    /// action logs and referenced by the parent ID of actions in progress
+    fn action_ids(&mut self) -> &mut IdAllocator;
+
+    /// Reserves the ID of a new action or GLU
+    fn next_action_id(&mut self) -> i32 {
+        self.action_ids().next_id() as i32
+    }
 
     fn game_status(&self) -> &GameResult;
 
@@ -30,6 +68,155 @@ pub trait GameState {
 
     fn set_player_result(&mut self, player_result: GameResult, id: usize);
 
+    /// Returns true if the player has been eliminated from the game (lost, disqualified or timed
+    /// out) while the game is still going on
+    fn is_player_eliminated(&self, player: usize) -> bool {
+        self.player_results()[player].is_elimination()
+    }
+
+    /// Returns the players still in the game, in player order. Eliminated players are excluded
+    /// from turn order and from decisions taken by several players at once.
+    fn active_players(&self) -> Vec<usize> {
+        (0..self.n_players() as usize)
+            .filter(|p| !self.is_player_eliminated(*p))
+            .collect()
+    }
+
+    /// Returns the teams with at least one player still in the game
+    fn active_teams(&self) -> Vec<usize> {
+        (0..self.n_teams())
+            .filter(|t| {
+                self.team_members(*t)
+                    .iter()
+                    .any(|p| !self.is_player_eliminated(*p))
+            })
+            .collect()
+    }
+
+    /// Returns the first player after the given one (in turn order) that is still in the game,
+    /// or None if all players have been eliminated
+    fn next_active_player(&self, player: usize) -> Option<usize> {
+        let n_players = self.n_players() as usize;
+        (1..=n_players)
+            .map(|i| (player + i) % n_players)
+            .find(|p| !self.is_player_eliminated(*p))
+    }
+
+    /// Passes the turn to the next player still in the game (which may be the turn owner itself,
+    /// if it is the only one left). Returns false, leaving the turn owner unchanged, if all
+    /// players have been eliminated. Shared by ForwardModel::end_player_turn and PassTurn.
+    fn end_player_turn(&mut self) -> bool {
+        match self.next_active_player(self.turn_owner() as usize) {
+            Some(next) => {
+                self.set_turn_owner(next as i8);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the number of teams in the game (by default, each player is its own team)
+    fn n_teams(&self) -> usize;
+
+    /// Returns the team the player belongs to
+    fn team_of(&self, player: usize) -> usize;
+
+    /// Assigns players to teams: `teams[p]` is the team of player `p`, and team IDs go from 0 to
+    /// the number of teams - 1
+    fn set_teams(&mut self, teams: Vec<usize>);
+
+    /// Returns all the players in the team, in player order
+    fn team_members(&self, team: usize) -> Vec<usize> {
+        (0..self.n_players() as usize)
+            .filter(|p| self.team_of(*p) == team)
+            .collect()
+    }
+
+    /// Returns the other players in the same team as the given player
+    fn teammates(&self, player: usize) -> Vec<usize> {
+        self.team_members(self.team_of(player))
+            .into_iter()
+            .filter(|p| *p != player)
+            .collect()
+    }
+
+    /// Sets the same result for every player in the team
+    fn set_team_result(&mut self, team_result: GameResult, team: usize) {
+        for p in self.team_members(team) {
+            self.set_player_result(team_result.clone(), p);
+        }
+    }
+
+    /// Returns the score of a player
+    fn game_score(&self, player: usize) -> f64;
+
+    fn set_game_score(&mut self, player: usize, score: f64);
+
+    /// Returns the score of a team, shared by all its members (sum of the scores of its players)
+    fn team_score(&self, team: usize) -> f64 {
+        self.team_members(team)
+            .iter()
+            .map(|p| self.game_score(*p))
+            .sum()
+    }
+
+    /// Returns the position of the player (1 is first) ranked by team score. Teams with the same
+    /// score share the same position, and all the members of a team share their team's position.
+    fn ordinal_position(&self, player: usize) -> usize {
+        let score = self.team_score(self.team_of(player));
+        1 + (0..self.n_teams())
+            .filter(|t| self.team_score(*t) > score)
+            .count()
+    }
+
+    /// Returns true if teammates can observe each other's hidden information
+    fn share_team_information(&self) -> bool;
+
+    fn set_share_team_information(&mut self, share: bool);
+
+    /// Returns true if the player can observe information only visible to the given owner
+    /// (see VisibilityMode::VisibleToOwner): the owner itself, and its teammates when team
+    /// information is shared. Components owned by the game (-1) are never observable this way.
+    fn can_observe_owner(&self, player: usize, owner_id: i32) -> bool {
+        if owner_id < 0 {
+            return false;
+        }
+        let owner = owner_id as usize;
+        owner == player
+            || (self.share_team_information() && self.team_of(owner) == self.team_of(player))
+    }
+
+    /// Gives the component to a new owner (-1 for the game) and records the change in the
+    /// ownership history. Returns false, recording nothing, if it already had that owner.
+    fn transfer_ownership(&mut self, component: &mut dyn Component, new_owner: i32) -> bool;
+
+    /// Same as transfer_ownership, for a component held (at any depth) by the registered
+    /// container with the given ID, or for the registered component itself if both IDs are
+    /// equal. Returns false if the component can't be found (see
+    /// IComponentContainer::set_owner_of) or already had that owner.
+    fn transfer_ownership_by_id(
+        &mut self,
+        container_id: usize,
+        component_id: usize,
+        new_owner: i32,
+    ) -> bool;
+
+    /// Ownership changes made with transfer_ownership in this game, oldest first
+    fn ownership_history(&self) -> &[OwnershipChange];
+
+    /// Registers a component of the game (a deck, a board, a counter...) so that actions can
+    /// refer to it by ID. Returns the component previously registered with the same ID, if any.
+    fn add_component(&mut self, component: Box<dyn Component>) -> Option<Box<dyn Component>>;
+
+    /// Returns the registered component with the given ID. Components nested inside another one
+    /// (e.g. the cards of a deck) are reached through their container.
+    fn get_component(&self, component_id: usize) -> Option<&dyn Component>;
+
+    fn get_component_mut(&mut self, component_id: usize) -> Option<&mut dyn Component>;
+
+    /// IDs of the registered components, in ascending order
+    fn registered_component_ids(&self) -> Vec<usize>;
+
     fn reset(&mut self);
 
     fn remove_completed_actions_in_progress(&mut self);
@@ -46,6 +233,17 @@ pub trait GameState {
     */
 }
 
+impl<'a> dyn GameState + 'a {
+    /// Returns the registered component with the given ID if it is of type C
+    pub fn component<C: Component>(&self, component_id: usize) -> Option<&C> {
+        self.get_component(component_id)?.downcast_ref::<C>()
+    }
+
+    pub fn component_mut<C: Component>(&mut self, component_id: usize) -> Option<&mut C> {
+        self.get_component_mut(component_id)?.downcast_mut::<C>()
+    }
+}
+
 /**
  * Represents the state of the game, containing necessary information about the game.
  * This struct is distinct from the Game struct, which also controls the players and other components not present here.
@@ -55,22 +253,36 @@ pub struct AbstractGameState {
     game_parameters: Box<dyn GameParameters>,
     game_status: GameResult,
     player_results: Vec<GameResult>,
+    // Team of each player, and whether teammates can see each other's hidden information
+    player_teams: Vec<usize>,
+    share_team_information: bool,
+    player_scores: Vec<f64>,
     turn_owner: i8,
+    // Seeds of this game, used to create all random number generators
+    seeds: GameSeeds,
     // Main RNG used for all random number generation in the game
-    // TODO: Review later to check if seed is need
-    rnd: ThreadRng,
-
-    // RNG used exclusively for redetermination - not seeded, and doesn't affect the game state
-    redetermination_rnd: ThreadRng,
+    rnd: StdRng,
+
+    // Number of redeterminations done so far, each one uses its own seed
+    redetermination_count: u64,
+    // IDs of the components, actions and GLUs of this game, so the same seed always yields the
+    // same IDs
+    component_ids: IdAllocator,
+    action_ids: IdAllocator,
+    ownership_history: Vec<OwnershipChange>,
+    // Components registered with add_component, by ID
+    components: BTreeMap<usize, Box<dyn Component>>,
 
     // Vec of Vec to represent available actions for each player
+    #[allow(dead_code)]
     player_actions_available: Vec<Vec<Box<dyn ActionType>>>,
     actions_in_progress: VecDeque<ExtendedGLU>,
 }
 
 impl AbstractGameState {
     /// Constructor for AbstractGameState
-    pub fn new(game_parameters: Box<dyn GameParameters>, n_players: u8) -> Self {
+    pub fn new(game_parameters: Box<dyn GameParameters>, n_players: u8, seed: u64) -> Self {
+        let seeds = GameSeeds::new(seed);
         let mut player_actions_available = Vec::with_capacity(n_players as usize);
 
         for _ in 0..n_players {
@@ -82,15 +294,63 @@ impl AbstractGameState {
             game_parameters,
             game_status: GameResult::GameOngoing,
             player_results: vec![GameResult::GameOngoing; n_players as usize],
+            player_teams: (0..n_players as usize).collect(),
+            share_team_information: false,
+            player_scores: vec![0.0; n_players as usize],
             turn_owner: 0,
             player_actions_available,
             actions_in_progress: VecDeque::new(), // Initializing actions_in_progress
-            rnd: rand::thread_rng(),
-            redetermination_rnd: rand::thread_rng(),
+            rnd: seeds.game_rnd(),
+            seeds,
+            redetermination_count: 0,
+            component_ids: IdAllocator::new(),
+            action_ids: IdAllocator::new(),
+            ownership_history: Vec::new(),
+            components: BTreeMap::new(),
         }
     }
 
+    /// Returns a copy of the state that continues from the same point: same RNG state and same
+    /// ID allocators, so components and actions created in the copy get the IDs they would have
+    /// got in the original. Available actions are not copied, as they are recomputed every turn.
+    pub fn copy(&self) -> Self {
+        AbstractGameState {
+            n_players: self.n_players,
+            game_parameters: self.game_parameters.clone(),
+            game_status: self.game_status.clone(),
+            player_results: self.player_results.clone(),
+            player_teams: self.player_teams.clone(),
+            share_team_information: self.share_team_information,
+            player_scores: self.player_scores.clone(),
+            turn_owner: self.turn_owner,
+            seeds: self.seeds,
+            rnd: self.rnd.clone(),
+            redetermination_count: self.redetermination_count,
+            component_ids: self.component_ids.clone(),
+            action_ids: self.action_ids.clone(),
+            ownership_history: self.ownership_history.clone(),
+            components: self.components.clone(),
+            player_actions_available: (0..self.n_players).map(|_| Vec::new()).collect(),
+            actions_in_progress: self.actions_in_progress.clone(),
+        }
+    }
+
+    /// Copy of the state as the player could believe it to be: every registered component is
+    /// replaced by its perspective copy (see Component::perspective_copy), which hides or
+    /// redeterminises what the player can't see. Uses the next redetermination random source.
+    pub fn copy_for_player(&mut self, player: usize) -> Self {
+        let mut rnd = self.redetermination_rnd();
+        let mut copy = self.copy();
+        for (id, component) in &self.components {
+            if let Some(seen) = component.perspective_copy(self, player, &mut rnd) {
+                copy.components.insert(*id, seen);
+            }
+        }
+        copy
+    }
+
     // Gets the index of the action in progress by its ID
+    #[allow(dead_code)]
     fn get_index_of_action_in_progress(&self, id: i32) -> Option<usize> {
         let mut result: Option<usize> = None;
         for (idx, seq) in self.actions_in_progress.iter().enumerate() {
@@ -115,12 +375,38 @@ impl GameState for AbstractGameState {
         self.turn_owner
     }
 
+    fn set_turn_owner(&mut self, turn_owner: i8) {
+        self.turn_owner = turn_owner;
+    }
+
     fn n_players(&self) -> u8 {
         self.n_players
     }
 
-    fn rnd(&self) -> ThreadRng {
-        self.rnd.clone()
+    fn rnd(&mut self) -> &mut StdRng {
+        &mut self.rnd
+    }
+
+    fn seeds(&self) -> GameSeeds {
+        self.seeds
+    }
+
+    fn redetermination_rnd(&mut self) -> StdRng {
+        let rnd = self.seeds.redetermination_rnd(self.redetermination_count);
+        self.redetermination_count += 1;
+        rnd
+    }
+
+    fn redetermination_count(&self) -> u64 {
+        self.redetermination_count
+    }
+
+    fn component_ids(&mut self) -> &mut IdAllocator {
+        &mut self.component_ids
+    }
+
+    fn action_ids(&mut self) -> &mut IdAllocator {
+        &mut self.action_ids
     }
 
     fn game_status(&self) -> &GameResult {
@@ -139,11 +425,121 @@ impl GameState for AbstractGameState {
         self.player_results[id] = player_result;
     }
 
+    fn n_teams(&self) -> usize {
+        self.player_teams.iter().max().map_or(0, |t| t + 1)
+    }
+
+    fn team_of(&self, player: usize) -> usize {
+        self.player_teams[player]
+    }
+
+    fn set_teams(&mut self, teams: Vec<usize>) {
+        assert_eq!(
+            teams.len(),
+            self.n_players as usize,
+            "Every player must be assigned to a team"
+        );
+        let n_teams = teams.iter().max().map_or(0, |t| t + 1);
+        assert!(
+            (0..n_teams).all(|t| teams.contains(&t)),
+            "Team IDs must go from 0 to the number of teams - 1"
+        );
+        self.player_teams = teams;
+    }
+
+    fn game_score(&self, player: usize) -> f64 {
+        self.player_scores[player]
+    }
+
+    fn set_game_score(&mut self, player: usize, score: f64) {
+        self.player_scores[player] = score;
+    }
+
+    fn share_team_information(&self) -> bool {
+        self.share_team_information
+    }
+
+    fn set_share_team_information(&mut self, share: bool) {
+        self.share_team_information = share;
+    }
+
+    fn transfer_ownership(&mut self, component: &mut dyn Component, new_owner: i32) -> bool {
+        let from = component.owner_id();
+        if from == new_owner {
+            return false;
+        }
+        component.set_owner_id(new_owner);
+        self.ownership_history.push(OwnershipChange {
+            component_id: component.component_id(),
+            from,
+            to: new_owner,
+        });
+        true
+    }
+
+    fn transfer_ownership_by_id(
+        &mut self,
+        container_id: usize,
+        component_id: usize,
+        new_owner: i32,
+    ) -> bool {
+        let Some(container) = self.components.get_mut(&container_id) else {
+            return false;
+        };
+        let from = if container_id == component_id {
+            let from = container.owner_id();
+            container.set_owner_id(new_owner);
+            Some(from)
+        } else {
+            container
+                .as_container_mut()
+                .and_then(|c| c.set_owner_of(component_id, new_owner))
+        };
+        match from {
+            Some(from) if from != new_owner => {
+                self.ownership_history.push(OwnershipChange {
+                    component_id,
+                    from,
+                    to: new_owner,
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn ownership_history(&self) -> &[OwnershipChange] {
+        &self.ownership_history
+    }
+
+    fn add_component(&mut self, component: Box<dyn Component>) -> Option<Box<dyn Component>> {
+        self.components.insert(component.component_id(), component)
+    }
+
+    fn get_component(&self, component_id: usize) -> Option<&dyn Component> {
+        self.components.get(&component_id).map(|c| c.as_ref())
+    }
+
+    fn get_component_mut(&mut self, component_id: usize) -> Option<&mut dyn Component> {
+        self.components.get_mut(&component_id).map(|c| c.as_mut())
+    }
+
+    fn registered_component_ids(&self) -> Vec<usize> {
+        self.components.keys().copied().collect()
+    }
+
     fn reset(&mut self) {
         self.game_status = GameResult::GameOngoing;
         self.player_results = vec![GameResult::GameOngoing; self.n_players as usize];
+        self.player_scores = vec![0.0; self.n_players as usize];
         self.turn_owner = 0;
         self.actions_in_progress.clear();
+        self.rnd = self.seeds.game_rnd();
+        self.redetermination_count = 0;
+        self.component_ids.reset();
+        self.action_ids.reset();
+        self.ownership_history.clear();
+        self.components.clear();
     }
 
     // Removes actions from the stack that are marked as completed
@@ -222,3 +618,256 @@ impl GameState for AbstractGameState {
 
     */
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        actions::{action::Action, do_nothing::DoNothing},
+        components::{area::Area, card::Card, deck::Deck, token::Token},
+        core_constants::VisibilityMode,
+        game_parameters::AbstractParameters,
+        glu::glu_type::ExtendedGLUType,
+        interfaces::component_container::IComponentContainer,
+    };
+
+    fn new_state(n_players: u8) -> AbstractGameState {
+        AbstractGameState::new(Box::new(AbstractParameters::new()), n_players, 0)
+    }
+
+    #[test]
+    fn test_default_teams() {
+        let state = new_state(3);
+        assert_eq!(state.n_teams(), 3);
+        assert_eq!(state.team_of(2), 2);
+        assert!(state.teammates(1).is_empty());
+    }
+
+    #[test]
+    fn test_team_results_and_ranking() {
+        let mut state = new_state(4);
+        state.set_teams(vec![0, 1, 0, 1]);
+        assert_eq!(state.n_teams(), 2);
+        assert_eq!(state.team_members(1), vec![1, 3]);
+        assert_eq!(state.teammates(0), vec![2]);
+
+        state.set_game_score(0, 3.0);
+        state.set_game_score(1, 5.0);
+        state.set_game_score(2, 4.0);
+        state.set_game_score(3, 1.0);
+        assert_eq!(state.team_score(0), 7.0);
+        assert_eq!(state.ordinal_position(2), 1);
+        assert_eq!(state.ordinal_position(1), 2);
+
+        state.set_team_result(GameResult::WinGame, 0);
+        state.set_team_result(GameResult::LoseGame, 1);
+        assert_eq!(
+            state.player_results(),
+            &vec![
+                GameResult::WinGame,
+                GameResult::LoseGame,
+                GameResult::WinGame,
+                GameResult::LoseGame
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_teams_must_be_contiguous() {
+        let mut state = new_state(2);
+        state.set_teams(vec![0, 2]);
+    }
+
+    #[test]
+    fn test_eliminated_players_are_skipped() {
+        let mut state = new_state(4);
+        state.set_player_result(GameResult::LoseGame, 1);
+        state.set_player_result(GameResult::Disqualify, 2);
+        assert!(state.is_player_eliminated(1));
+        assert_eq!(state.active_players(), vec![0, 3]);
+        assert_eq!(state.next_active_player(0), Some(3));
+        assert_eq!(state.next_active_player(3), Some(0));
+
+        state.set_teams(vec![0, 1, 1, 0]);
+        assert_eq!(state.active_teams(), vec![0]);
+
+        assert!(state.end_player_turn());
+        assert_eq!(state.turn_owner(), 3);
+        state.set_player_result(GameResult::LoseGame, 0);
+        assert!(state.end_player_turn());
+        assert_eq!(state.turn_owner(), 3);
+        state.set_player_result(GameResult::LoseGame, 3);
+        assert!(!state.end_player_turn());
+        assert_eq!(state.turn_owner(), 3);
+    }
+
+    #[test]
+    fn test_component_ids_are_per_game() {
+        let mut state = new_state(2);
+        let mut other = new_state(2);
+        let token = Token::new("Meeple", state.component_ids());
+        assert_eq!(token.component_id(), 0);
+        assert_eq!(state.next_component_id(), 1);
+        assert_eq!(other.next_component_id(), 0);
+
+        // Copies continue from the same point, without affecting the original
+        let mut copy = state.copy();
+        assert_eq!(copy.next_component_id(), 2);
+        assert_eq!(copy.next_component_id(), 3);
+        assert_eq!(state.next_component_id(), 2);
+
+        state.reset();
+        assert_eq!(state.next_component_id(), 0);
+    }
+
+    #[test]
+    fn test_action_ids_are_per_game() {
+        let mut state = new_state(2);
+        let action = DoNothing::new(state.action_ids());
+        assert_eq!(action.id(), 0);
+        let mut glu = ExtendedGLU::new(ExtendedGLUType::Default, state.action_ids());
+        glu.parent_id = action.id();
+        state.actions_in_progress().push_back(glu);
+
+        // The copy keeps the in-progress GLU and allocates the same IDs as the original
+        let mut copy = state.copy();
+        assert_eq!(copy.actions_in_progress()[0].get_id(), 1);
+        assert_eq!(copy.actions_in_progress()[0].parent_id(), 0);
+        assert_eq!(copy.next_action_id(), 2);
+        assert_eq!(state.next_action_id(), 2);
+
+        // Component and action IDs are independent
+        assert_eq!(state.next_component_id(), 0);
+        state.reset();
+        assert_eq!(state.next_action_id(), 0);
+    }
+
+    #[test]
+    fn test_can_observe_owner() {
+        let mut state = new_state(4);
+        state.set_teams(vec![0, 1, 0, 1]);
+        assert!(state.can_observe_owner(0, 0));
+        assert!(!state.can_observe_owner(0, 2));
+        assert!(!state.can_observe_owner(0, -1));
+
+        state.set_share_team_information(true);
+        assert!(state.can_observe_owner(0, 2));
+        assert!(!state.can_observe_owner(0, 1));
+    }
+
+    #[test]
+    fn test_ownership_transfers_are_recorded() {
+        let mut state = new_state(3);
+        let mut token = Token::new("Meeple", state.component_ids());
+        assert!(state.transfer_ownership(&mut token, 1));
+        assert!(!state.transfer_ownership(&mut token, 1));
+        assert!(state.transfer_ownership(&mut token, -1));
+        assert_eq!(token.owner_id(), -1);
+        assert_eq!(
+            state.ownership_history(),
+            &[
+                OwnershipChange {
+                    component_id: 0,
+                    from: -1,
+                    to: 1
+                },
+                OwnershipChange {
+                    component_id: 0,
+                    from: 1,
+                    to: -1
+                }
+            ]
+        );
+
+        assert_eq!(state.copy().ownership_history().len(), 2);
+        state.reset();
+        assert!(state.ownership_history().is_empty());
+    }
+
+    #[test]
+    fn test_transfer_ownership_by_id() {
+        let mut state = new_state(2);
+        let mut inner = Area::new_with_id(-1, 10);
+        inner.put_component(Box::new(Token::new_with_id("Meeple", 1)));
+        let mut board = Area::new_with_id(-1, 100);
+        board.put_component(Box::new(inner));
+        state.add_component(Box::new(board));
+
+        assert!(state.transfer_ownership_by_id(100, 1, 0));
+        assert!(!state.transfer_ownership_by_id(100, 1, 0));
+        assert!(!state.transfer_ownership_by_id(100, 99, 0));
+        assert!(state.transfer_ownership_by_id(100, 100, 1));
+        let board = (&state as &dyn GameState).component::<Area>(100).unwrap();
+        assert_eq!(board.owner_id(), 1);
+        assert_eq!(board.get_component(1).unwrap().owner_id(), 0);
+        let inner = board.get_component(10).unwrap().downcast_ref::<Area>();
+        assert_eq!(inner.unwrap().get_component(1).unwrap().owner_id(), 0);
+        assert_eq!(
+            state.ownership_history().last(),
+            Some(&OwnershipChange {
+                component_id: 100,
+                from: -1,
+                to: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_transfer_ownership_by_id_in_other_containers() {
+        let mut state = new_state(2);
+        let mut hand = Deck::new_with_id("Hand", -1, 20, VisibilityMode::VisibleToOwner);
+        hand.add_to_bottom(Card::new_with_id("Card", 2));
+        state.add_component(Box::new(hand.clone()));
+        let mut table = Area::new_with_id(-1, 200);
+        hand.add_to_bottom(Card::new_with_id("Card", 3));
+        table.put_component(Box::new(hand));
+        state.add_component(Box::new(table));
+
+        assert!(state.transfer_ownership_by_id(20, 2, 1));
+        let hand = (&state as &dyn GameState).component::<Deck<Card>>(20);
+        assert_eq!(hand.unwrap().get_components()[0].owner_id(), 1);
+
+        assert!(state.transfer_ownership_by_id(200, 3, 0));
+        let table = (&state as &dyn GameState).component::<Area>(200).unwrap();
+        assert_eq!(table.get_component(3).unwrap().owner_id(), 0);
+        let hand = table.get_component(20).unwrap().as_container().unwrap();
+        assert_eq!(hand.get_components()[1].owner_id(), 0);
+    }
+
+    #[test]
+    fn test_copy_for_player_hides_components() {
+        let mut state = new_state(2);
+        let mut secret = Area::new_with_id(-1, 10);
+        secret.put_component(Box::new(Token::new_with_id("Meeple", 1)));
+        secret.set_visibility_mode(VisibilityMode::VisibleToOwner);
+        secret.set_owner_id(0);
+        let mut table = Area::new_with_id(-1, 100);
+        table.put_component(Box::new(secret));
+        table.put_component(Box::new(Token::new_with_id("Meeple", 2)));
+        state.add_component(Box::new(table));
+
+        let seen_by = |state: &mut AbstractGameState, player: usize| -> Vec<usize> {
+            let copy = state.copy_for_player(player);
+            let table = copy.get_component(100).unwrap().as_container().unwrap();
+            let mut ids: Vec<usize> = table
+                .nested_components()
+                .iter()
+                .map(|c| c.component_id())
+                .collect();
+            ids.sort();
+            ids
+        };
+        assert_eq!(seen_by(&mut state, 0), vec![1, 2, 10]);
+        assert_eq!(seen_by(&mut state, 1), vec![2, 10]);
+        assert_eq!(state.redetermination_count(), 2);
+        assert_eq!(
+            (&state as &dyn GameState)
+                .component::<Area>(100)
+                .unwrap()
+                .nested_components()
+                .len(),
+            3
+        );
+    }
+}
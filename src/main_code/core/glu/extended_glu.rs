@@ -1,7 +1,6 @@
 use crate::main_code::core::actions::action::Action;
 use crate::main_code::core::{
-    game_state::GameState,
-    glu::{glu::GLU, glu_type::ExtendedGLUType},
+    game_state::GameState, glu::glu_type::ExtendedGLUType, id_allocator::IdAllocator,
 };
 
 #[derive(Clone)]
@@ -14,9 +13,10 @@ pub struct ExtendedGLU {
 }
 
 impl ExtendedGLU {
-    pub fn new(glu_type: ExtendedGLUType) -> Self {
+    /// Creates a GLU whose ID is taken from the action IDs of the game (see GameState::action_ids)
+    pub fn new(glu_type: ExtendedGLUType, ids: &mut IdAllocator) -> Self {
         ExtendedGLU {
-            id: GLU::next_id(),
+            id: ids.next_id() as i32,
             glu_type,
             parent_id: -1,
             player_id: -1,
@@ -51,8 +51,10 @@ pub trait ExtendedGluTrait {
         player_id: usize,
         parent_id: Option<usize>,
     ) -> bool;
+    #[allow(clippy::borrowed_box)]
     fn can_execute(&self, game_state: &Box<dyn GameState>, player_id: usize) -> bool;
 
+    #[allow(clippy::borrowed_box)]
     fn compute_available_actions(&self, game_state: &Box<dyn GameState>) -> Vec<Box<dyn Action>>;
 }
 
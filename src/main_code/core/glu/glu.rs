@@ -1,8 +1,6 @@
-use crate::main_code::core::{game_state::GameState, glu::glu_type::GLUType};
-use std::sync::atomic::{AtomicUsize, Ordering};
-
-/// Static atomic counter for generating unique IDs across GLUs
-static ID_FOUNTAIN: AtomicUsize = AtomicUsize::new(0);
+use crate::main_code::core::{
+    game_state::GameState, glu::glu_type::GLUType, id_allocator::IdAllocator,
+};
 
 #[derive(Clone, Default, Debug)]
 pub struct GLU {
@@ -11,18 +9,14 @@ pub struct GLU {
 }
 
 impl GLU {
-    pub fn new(glu_type: GLUType) -> Self {
+    /// Creates a GLU whose ID is taken from the action IDs of the game (see GameState::action_ids)
+    pub fn new(glu_type: GLUType, ids: &mut IdAllocator) -> Self {
         GLU {
-            id: Self::next_id(),
+            id: ids.next_id() as i32,
             glu_type,
         }
     }
 
-    /// Generates the next unique ID using an atomic counter
-    pub fn next_id() -> i32 {
-        ID_FOUNTAIN.fetch_add(1, Ordering::Relaxed) as i32
-    }
-
     pub fn get_id(&self) -> i32 {
         self.id
     }
@@ -39,6 +33,7 @@ pub trait GluTrait {
         player_id: usize,
         parent_id: Option<usize>,
     ) -> bool;
+    #[allow(clippy::borrowed_box)]
     fn can_execute(&self, game_state: &Box<dyn GameState>, player_id: usize) -> bool;
 
     // TODO: Añadir execute del propio trait
@@ -19,12 +19,14 @@ pub enum ExtendedGLUType {
     Default,
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for GLUType {
     fn default() -> Self {
         GLUType::Default
     }
 }
 
+#[allow(clippy::derivable_impls)]
 impl Default for ExtendedGLUType {
     fn default() -> Self {
         ExtendedGLUType::Default
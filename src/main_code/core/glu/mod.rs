@@ -1,3 +1,4 @@
 pub mod extended_glu;
+#[allow(clippy::module_inception)]
 pub mod glu;
 pub mod glu_type;
@@ -0,0 +1,33 @@
+/// Hands out unique IDs (of components, actions or GLUs). Each game state owns its allocators, so
+/// the IDs of a game only depend on the order in which it creates things, not on anything else
+/// running in the process.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct IdAllocator {
+    next_id: usize,
+}
+
+impl IdAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Allocator whose first ID is the given one, e.g. to avoid IDs reserved by hand
+    pub fn starting_at(next_id: usize) -> Self {
+        Self { next_id }
+    }
+
+    pub fn next_id(&mut self) -> usize {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// ID that will be handed out next
+    pub fn peek(&self) -> usize {
+        self.next_id
+    }
+
+    pub fn reset(&mut self) {
+        self.next_id = 0;
+    }
+}
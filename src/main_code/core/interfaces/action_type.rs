@@ -4,5 +4,6 @@ pub trait ActionType {
     fn name(&self) -> &str;
     fn parse_string(&self, value: &str) -> Box<dyn ActionType>;
     fn get_default(&self) -> Box<dyn ActionType>;
+    #[allow(clippy::borrowed_box)]
     fn can_execute(&self, game_state: &Box<dyn GameState>, player: usize) -> bool;
 }
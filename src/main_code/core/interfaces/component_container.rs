@@ -1,21 +1,46 @@
 use crate::main_code::core::{components::component::Component, core_constants::VisibilityMode};
 
 /// A trait to be used on any Component that contains other Components.
-/// The trait is 'read-only', and deliberately avoids specifying add/remove type methods.
+/// The trait is 'read-only' (apart from the visibility mode and the owners of the contents), and
+/// deliberately avoids specifying add/remove type methods.
 /// The purposes are:
 ///
 /// i) To be used to gather information about game states for game metrics and comparisons.
-/// ii) To indicate who can see the contents of the Container (Everyone, No-one, just the Owner).
+/// ii) To indicate who can see the contents of the Container (Everyone, No-one, just the
+/// Owner given by Component::owner_id).
 /// iii) As a holder of useful stream-related default methods - these are all read-only methods.
-pub trait IComponentContainer {
-    /// Returns a vector of all the Components in the Container
+pub trait IComponentContainer: Component {
+    /// Returns a vector of all the Components in the Container. The order must be deterministic
+    /// (e.g. by position, insertion or ID), so that seeded games and replays behave identically
+    /// from run to run.
     fn get_components(&self) -> Vec<Box<dyn Component>>;
 
     /// Returns the visibility mode of the Container
     fn get_visibility_mode(&self) -> VisibilityMode;
 
+    /// Changes who can see the contents of the Container
+    fn set_visibility_mode(&mut self, visibility_mode: VisibilityMode);
+
+    /// Changes the owner of the component with the given ID held by the Container, at any depth,
+    /// without recording it (see GameState::transfer_ownership_by_id). Returns its previous
+    /// owner, or None if the Container doesn't hold it.
+    fn set_owner_of(&mut self, component_id: usize, owner_id: i32) -> Option<i32>;
+
+    /// Returns true if the player can see the Component at the given index (in get_components
+    /// order). Only used in MixedVisibility mode, the rest of modes are resolved by
+    /// visibility::is_visible_at.
+    fn is_visible_at(&self, _index: usize, _player: usize) -> bool {
+        false
+    }
+
     /// Returns the size of the container (number of components)
     fn get_size(&self) -> usize {
         self.get_components().len()
     }
+
+    /// Returns the number of items held by the container, counting each fungible token (see
+    /// TokenPool) separately. Same as get_size for containers of individual components.
+    fn total_count(&self) -> u64 {
+        self.get_size() as u64
+    }
 }
@@ -1,5 +1,6 @@
 use crate::main_code::core::{actions::action::Action, game_state::GameState};
 
+#[allow(clippy::borrowed_box)]
 pub trait ExtendedSequence {
     /// Returns a list of available actions for the current player
     fn compute_available_actions(&self, state: &Box<dyn GameState>) -> Vec<Box<dyn Action>>;
@@ -50,6 +51,7 @@ pub trait ExtendedSequence {
     }
 }
 
+#[allow(clippy::borrowed_box)]
 pub trait Spawnable {
     fn notify_completion(&self, state: &Box<dyn GameState>);
 }
@@ -2,3 +2,4 @@ pub mod action_type;
 pub mod component_container;
 pub mod extended_sequence;
 pub mod printable;
+pub mod renderable;
@@ -15,6 +15,6 @@ pub trait IPrintable<TGameState: GameState>: fmt::Display + fmt::Debug {
      * Prints itself to console.
      */
     fn print_to_console(&self) {
-        println!("{}", self.to_string());
+        println!("{}", self);
     }
 }
@@ -0,0 +1,40 @@
+use crate::main_code::core::{
+    game_state::GameState, interfaces::component_container::IComponentContainer, visibility,
+};
+
+/// Text representations of a Component, used for logs, console play and debugging.
+pub trait IRenderable {
+    /// One-line summary, e.g. the name of a card or the size of a deck
+    fn short_string(&self) -> String;
+
+    /// Full description, including contents and properties
+    fn detailed_string(&self) -> String {
+        self.short_string()
+    }
+
+    /// Description as seen by the player, with the contents they cannot see shown as '?'
+    fn string_for_player(&self, _gs: &dyn GameState, _player: usize) -> String {
+        self.detailed_string()
+    }
+}
+
+/// Renders each component of the container as seen by the player: visible components with their
+/// own perspective form (so nested containers hide their contents too) and hidden ones as '?'.
+pub fn contents_for_player<C: IComponentContainer + ?Sized>(
+    gs: &dyn GameState,
+    container: &C,
+    player: usize,
+) -> Vec<String> {
+    container
+        .get_components()
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            if visibility::is_visible_at(gs, container, i, player) {
+                c.string_for_player(gs, player)
+            } else {
+                "?".to_string()
+            }
+        })
+        .collect()
+}
@@ -6,5 +6,11 @@ pub mod game;
 pub mod game_parameters;
 pub mod game_state;
 pub mod glu;
+pub mod id_allocator;
 pub mod interfaces;
+pub mod ownership;
 pub mod player;
+pub mod properties;
+pub mod seeds;
+pub mod tunable_parameters;
+pub mod visibility;
@@ -0,0 +1,121 @@
+use crate::main_code::core::{
+    components::component::Component, core_constants::ComponentType,
+    interfaces::component_container::IComponentContainer,
+};
+use std::collections::BTreeMap;
+
+/// Change of owner of a component, as recorded by GameState::transfer_ownership.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct OwnershipChange {
+    pub component_id: usize,
+    pub from: i32,
+    pub to: i32,
+}
+
+/// Changes the owner of the component with the given ID if it is one of the components or is
+/// held by one of them (see IComponentContainer::set_owner_of). Returns its previous owner, or
+/// None if it isn't found.
+pub fn set_owner_among<'a>(
+    components: impl IntoIterator<Item = &'a mut dyn Component>,
+    component_id: usize,
+    owner_id: i32,
+) -> Option<i32> {
+    for c in components {
+        if c.component_id() == component_id {
+            let previous = c.owner_id();
+            c.set_owner_id(owner_id);
+            return Some(previous);
+        }
+        if let Some(previous) = c
+            .as_container_mut()
+            .and_then(|container| container.set_owner_of(component_id, owner_id))
+        {
+            return Some(previous);
+        }
+    }
+    None
+}
+
+/// Components of the container that match the predicate, including nested ones (e.g. cards in a
+/// deck inside an area), in ID order. Each component is returned once.
+pub fn find_components<C, F>(container: &C, predicate: F) -> Vec<Box<dyn Component>>
+where
+    C: IComponentContainer + ?Sized,
+    F: Fn(&dyn Component) -> bool,
+{
+    let mut found = BTreeMap::new();
+    let mut pending = container.get_components();
+    while let Some(c) = pending.pop() {
+        if found.contains_key(&c.component_id()) {
+            continue;
+        }
+        pending.extend(c.nested_components());
+        if predicate(c.as_ref()) {
+            found.insert(c.component_id(), c);
+        }
+    }
+    found.into_values().collect()
+}
+
+/// Components of the container owned by the given player (-1 for the game)
+pub fn owned_by<C: IComponentContainer + ?Sized>(
+    container: &C,
+    owner: i32,
+) -> Vec<Box<dyn Component>> {
+    find_components(container, |c| c.owner_id() == owner)
+}
+
+/// Components of the container of the given type
+pub fn of_type<C: IComponentContainer + ?Sized>(
+    container: &C,
+    component_type: ComponentType,
+) -> Vec<Box<dyn Component>> {
+    find_components(container, |c| c.component_type() == component_type)
+}
+
+/// Components of the container of the given type owned by the given player, e.g. the cards of
+/// player 2 when scoring
+pub fn owned_by_of_type<C: IComponentContainer + ?Sized>(
+    container: &C,
+    owner: i32,
+    component_type: ComponentType,
+) -> Vec<Box<dyn Component>> {
+    find_components(container, |c| {
+        c.owner_id() == owner && c.component_type() == component_type
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::components::{area::Area, card::Card, deck::Deck, token::Token};
+    use crate::main_code::core::core_constants::VisibilityMode;
+
+    #[test]
+    fn test_owner_queries() {
+        let mut hand = Deck::new_with_id("Hand", 2, 100, VisibilityMode::VisibleToOwner);
+        for id in 0..3 {
+            let mut card = Card::new_with_id("Card", id);
+            card.set_owner_id(2);
+            hand.add_to_bottom(card);
+        }
+        let mut token = Token::new_with_id("Meeple", 10);
+        token.set_owner_id(2);
+
+        let mut area = Area::new_with_id(-1, 200);
+        area.put_component(Box::new(hand));
+        area.put_component(Box::new(token));
+        area.put_component(Box::new(Card::new_with_id("Market", 5)));
+
+        let ids = |components: Vec<Box<dyn Component>>| -> Vec<usize> {
+            components.iter().map(|c| c.component_id()).collect()
+        };
+        assert_eq!(
+            ids(owned_by_of_type(&area, 2, ComponentType::Card)),
+            vec![0, 1, 2]
+        );
+        assert_eq!(ids(owned_by(&area, 2)), vec![0, 1, 2, 10, 100]);
+        assert_eq!(ids(owned_by(&area, -1)), vec![5]);
+        assert_eq!(ids(of_type(&area, ComponentType::Card)), vec![0, 1, 2, 5]);
+    }
+}
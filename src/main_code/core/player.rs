@@ -1,10 +1,14 @@
 use crate::main_code::core::{actions::action::Action, game_state::GameState};
 
 pub trait Player: dyn_clone::DynClone {
+    #[allow(clippy::ptr_arg)]
     fn next_action(
         &mut self,
         observation: &dyn GameState,
         actions: &Vec<Box<dyn Action>>,
     ) -> Box<dyn Action>;
+
+    /// Reseeds the random source of the player, if it has one
+    fn set_seed(&mut self, _seed: u64) {}
 }
 dyn_clone::clone_trait_object!(Player);
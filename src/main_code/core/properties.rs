@@ -0,0 +1,244 @@
+use std::{collections::BTreeMap, fmt};
+
+/// Typed value of a component property.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub enum PropertyValue {
+    Int(i64),
+    Text(String),
+    Bool(bool),
+    List(Vec<PropertyValue>),
+}
+
+impl PropertyValue {
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            PropertyValue::Int(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            PropertyValue::Text(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PropertyValue::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&Vec<PropertyValue>> {
+        match self {
+            PropertyValue::List(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PropertyValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropertyValue::Int(v) => write!(f, "{}", v),
+            PropertyValue::Text(v) => write!(f, "{}", v),
+            PropertyValue::Bool(v) => write!(f, "{}", v),
+            PropertyValue::List(values) => {
+                let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+                write!(f, "[{}]", values.join(", "))
+            }
+        }
+    }
+}
+
+impl From<i64> for PropertyValue {
+    fn from(value: i64) -> Self {
+        PropertyValue::Int(value)
+    }
+}
+
+impl From<i32> for PropertyValue {
+    fn from(value: i32) -> Self {
+        PropertyValue::Int(value.into())
+    }
+}
+
+impl From<&str> for PropertyValue {
+    fn from(value: &str) -> Self {
+        PropertyValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for PropertyValue {
+    fn from(value: String) -> Self {
+        PropertyValue::Text(value)
+    }
+}
+
+impl From<bool> for PropertyValue {
+    fn from(value: bool) -> Self {
+        PropertyValue::Bool(value)
+    }
+}
+
+impl<T: Into<PropertyValue>> From<Vec<T>> for PropertyValue {
+    fn from(values: Vec<T>) -> Self {
+        PropertyValue::List(values.into_iter().map(|v| v.into()).collect())
+    }
+}
+
+/// Key of a property: a stable hash of its name, so lookups do not compare strings. Keys can be
+/// computed once, e.g. `const COST: PropertyKey = PropertyKey::new("cost");`, and used wherever
+/// a property name is accepted.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct PropertyKey(u64);
+
+impl PropertyKey {
+    /// FNV-1a hash of the name, stable across runs and platforms
+    pub const fn new(name: &str) -> Self {
+        let bytes = name.as_bytes();
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut i = 0;
+        while i < bytes.len() {
+            hash ^= bytes[i] as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+            i += 1;
+        }
+        PropertyKey(hash)
+    }
+
+    pub fn hash_value(&self) -> u64 {
+        self.0
+    }
+}
+
+impl From<&str> for PropertyKey {
+    fn from(name: &str) -> Self {
+        PropertyKey::new(name)
+    }
+}
+
+impl From<&String> for PropertyKey {
+    fn from(name: &String) -> Self {
+        PropertyKey::new(name)
+    }
+}
+
+/// Bag of named, typed properties, indexed by the hash of their names. The names are kept for
+/// printing, and iteration follows name order, so it is deterministic.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct Properties {
+    values: BTreeMap<PropertyKey, (String, PropertyValue)>,
+}
+
+impl Properties {
+    pub fn new() -> Self {
+        Properties::default()
+    }
+
+    pub fn get(&self, key: impl Into<PropertyKey>) -> Option<&PropertyValue> {
+        self.values.get(&key.into()).map(|(_, v)| v)
+    }
+
+    pub fn get_mut(&mut self, key: impl Into<PropertyKey>) -> Option<&mut PropertyValue> {
+        self.values.get_mut(&key.into()).map(|(_, v)| v)
+    }
+
+    /// Sets a property, returning its previous value (if any)
+    pub fn set(&mut self, name: &str, value: PropertyValue) -> Option<PropertyValue> {
+        self.values
+            .insert(PropertyKey::new(name), (name.to_string(), value))
+            .map(|(_, v)| v)
+    }
+
+    pub fn remove(&mut self, key: impl Into<PropertyKey>) -> Option<PropertyValue> {
+        self.values.remove(&key.into()).map(|(_, v)| v)
+    }
+
+    pub fn contains(&self, key: impl Into<PropertyKey>) -> bool {
+        self.values.contains_key(&key.into())
+    }
+
+    /// Name of the property with the given key, if it is set
+    pub fn name_of(&self, key: PropertyKey) -> Option<&str> {
+        self.values.get(&key).map(|(name, _)| name.as_str())
+    }
+
+    pub fn get_int(&self, key: impl Into<PropertyKey>) -> Option<i64> {
+        self.get(key).and_then(|v| v.as_int())
+    }
+
+    pub fn get_text(&self, key: impl Into<PropertyKey>) -> Option<&str> {
+        self.get(key).and_then(|v| v.as_text())
+    }
+
+    pub fn get_bool(&self, key: impl Into<PropertyKey>) -> Option<bool> {
+        self.get(key).and_then(|v| v.as_bool())
+    }
+
+    pub fn get_list(&self, key: impl Into<PropertyKey>) -> Option<&Vec<PropertyValue>> {
+        self.get(key).and_then(|v| v.as_list())
+    }
+
+    /// Property names, in alphabetical order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    /// Properties, in alphabetical order of their names
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &PropertyValue)> {
+        let mut values: Vec<(&str, &PropertyValue)> = self
+            .values
+            .values()
+            .map(|(name, value)| (name.as_str(), value))
+            .collect();
+        values.sort_by(|a, b| a.0.cmp(b.0));
+        values.into_iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl fmt::Display for Properties {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values: Vec<String> = self.iter().map(|(k, v)| format!("{}: {}", k, v)).collect();
+        write!(f, "{{{}}}", values.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COST: PropertyKey = PropertyKey::new("cost");
+
+    #[test]
+    fn test_hashed_keys() {
+        assert_eq!(PropertyKey::new("cost"), PropertyKey::from("cost"));
+        assert_ne!(PropertyKey::new("cost"), PropertyKey::new("colour"));
+
+        let mut properties = Properties::new();
+        properties.set("cost", 3.into());
+        properties.set("colour", "Blue".into());
+        assert_eq!(properties.get_int(COST), Some(3));
+        assert_eq!(properties.get_int("cost"), Some(3));
+        assert_eq!(properties.name_of(COST), Some("cost"));
+
+        *properties.get_mut(COST).unwrap() = 5.into();
+        assert_eq!(
+            properties.keys().collect::<Vec<_>>(),
+            vec!["colour", "cost"]
+        );
+        assert_eq!(properties.to_string(), "{colour: Blue, cost: 5}");
+        assert_eq!(properties.remove(COST), Some(5.into()));
+        assert!(!properties.contains(COST));
+    }
+}
@@ -0,0 +1,147 @@
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Branches of the seed tree. Each branch derives independent seeds from the same parent.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum SeedDomain {
+    Game,
+    Player,
+    Redetermination,
+    Parameters,
+}
+
+impl SeedDomain {
+    fn tag(&self) -> u64 {
+        match self {
+            SeedDomain::Game => 1,
+            SeedDomain::Player => 2,
+            SeedDomain::Redetermination => 3,
+            SeedDomain::Parameters => 4,
+        }
+    }
+}
+
+/// SplitMix64 finalizer, used to scramble seeds so that nearby inputs give unrelated outputs.
+fn mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Deterministically derives the index-th child seed of the given domain from a parent seed.
+pub fn derive_seed(parent: u64, domain: SeedDomain, index: u64) -> u64 {
+    mix(mix(mix(parent) ^ domain.tag()) ^ index)
+}
+
+/// Root of the seed tree for a run of several games (e.g. a tournament). Every game seed is
+/// derived from the master seed and the index of the game, so a single game can be reproduced
+/// in isolation from its logged game seed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct SeedTree {
+    master_seed: u64,
+}
+
+impl SeedTree {
+    pub fn new(master_seed: u64) -> Self {
+        Self { master_seed }
+    }
+
+    pub fn master_seed(&self) -> u64 {
+        self.master_seed
+    }
+
+    pub fn game_seed(&self, game_index: u64) -> u64 {
+        derive_seed(self.master_seed, SeedDomain::Game, game_index)
+    }
+
+    pub fn game_seeds(&self, game_index: u64) -> GameSeeds {
+        GameSeeds::new(self.game_seed(game_index))
+    }
+}
+
+/// Seeds of a single game, all derived from its game seed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct GameSeeds {
+    game_seed: u64,
+}
+
+impl GameSeeds {
+    pub fn new(game_seed: u64) -> Self {
+        Self { game_seed }
+    }
+
+    /// Seed of the main RNG of the game state
+    pub fn game_seed(&self) -> u64 {
+        self.game_seed
+    }
+
+    /// Seed of the agent playing as the given player
+    pub fn player_seed(&self, player: usize) -> u64 {
+        derive_seed(self.game_seed, SeedDomain::Player, player as u64)
+    }
+
+    /// Root of the redetermination branch, from which every redetermination seed is derived
+    pub fn redetermination_root_seed(&self) -> u64 {
+        derive_seed(self.game_seed, SeedDomain::Redetermination, 0)
+    }
+
+    /// Seed of the index-th redetermination of the game state
+    pub fn redetermination_seed(&self, index: u64) -> u64 {
+        derive_seed(
+            self.redetermination_root_seed(),
+            SeedDomain::Redetermination,
+            index,
+        )
+    }
+
+    /// Seed used to sample the game parameters when they are randomized
+    pub fn parameters_seed(&self) -> u64 {
+        derive_seed(self.game_seed, SeedDomain::Parameters, 0)
+    }
+
+    pub fn game_rnd(&self) -> StdRng {
+        StdRng::seed_from_u64(self.game_seed)
+    }
+
+    pub fn player_rnd(&self, player: usize) -> StdRng {
+        StdRng::seed_from_u64(self.player_seed(player))
+    }
+
+    pub fn redetermination_rnd(&self, index: u64) -> StdRng {
+        StdRng::seed_from_u64(self.redetermination_seed(index))
+    }
+
+    pub fn parameters_rnd(&self) -> StdRng {
+        StdRng::seed_from_u64(self.parameters_seed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_derivation_is_deterministic() {
+        let tree = SeedTree::new(1234);
+        assert_eq!(tree.game_seed(9999), SeedTree::new(1234).game_seed(9999));
+        assert_eq!(
+            tree.game_seeds(5).player_seed(1),
+            GameSeeds::new(tree.game_seed(5)).player_seed(1)
+        );
+        assert_ne!(tree.game_seed(0), SeedTree::new(1235).game_seed(0));
+    }
+
+    #[test]
+    fn test_branches_are_distinct() {
+        let seeds = SeedTree::new(0).game_seeds(0);
+        let mut all = HashSet::new();
+        all.insert(seeds.game_seed());
+        all.insert(seeds.parameters_seed());
+        for i in 0..100 {
+            all.insert(seeds.player_seed(i as usize));
+            all.insert(seeds.redetermination_seed(i));
+        }
+        assert_eq!(all.len(), 202);
+    }
+}
@@ -0,0 +1,571 @@
+use rand::Rng;
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, fmt};
+
+/// Assignment of values to tunable parameters, keyed by parameter name.
+pub type ParameterAssignment = BTreeMap<String, ParameterValue>;
+
+/// Type of a tunable parameter.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ParameterType {
+    Int,
+    Float,
+    Bool,
+    Text,
+}
+
+impl fmt::Display for ParameterType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ParameterType::Int => "Int",
+            ParameterType::Float => "Float",
+            ParameterType::Bool => "Bool",
+            ParameterType::Text => "Text",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Value of a tunable parameter.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParameterValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Text(String),
+}
+
+impl ParameterValue {
+    pub fn parameter_type(&self) -> ParameterType {
+        match self {
+            ParameterValue::Int(_) => ParameterType::Int,
+            ParameterValue::Float(_) => ParameterType::Float,
+            ParameterValue::Bool(_) => ParameterType::Bool,
+            ParameterValue::Text(_) => ParameterType::Text,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            ParameterValue::Int(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            ParameterValue::Float(v) => Some(*v),
+            ParameterValue::Int(v) => Some(*v as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            ParameterValue::Bool(v) => Some(*v),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            ParameterValue::Text(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        match self {
+            ParameterValue::Int(v) => json!(v),
+            ParameterValue::Float(v) => json!(v),
+            ParameterValue::Bool(v) => json!(v),
+            ParameterValue::Text(v) => json!(v),
+        }
+    }
+
+    /// Reads a JSON value as a value of the expected type. Integers are accepted for Float
+    /// parameters; any other mismatch returns None.
+    pub fn from_json(value: &Value, expected: ParameterType) -> Option<ParameterValue> {
+        match expected {
+            ParameterType::Int => value.as_i64().map(ParameterValue::Int),
+            ParameterType::Float => value.as_f64().map(ParameterValue::Float),
+            ParameterType::Bool => value.as_bool().map(ParameterValue::Bool),
+            ParameterType::Text => value.as_str().map(|v| ParameterValue::Text(v.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ParameterValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterValue::Int(v) => write!(f, "{}", v),
+            ParameterValue::Float(v) => write!(f, "{}", v),
+            ParameterValue::Bool(v) => write!(f, "{}", v),
+            ParameterValue::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl From<i64> for ParameterValue {
+    fn from(value: i64) -> Self {
+        ParameterValue::Int(value)
+    }
+}
+
+impl From<i32> for ParameterValue {
+    fn from(value: i32) -> Self {
+        ParameterValue::Int(value.into())
+    }
+}
+
+impl From<f64> for ParameterValue {
+    fn from(value: f64) -> Self {
+        ParameterValue::Float(value)
+    }
+}
+
+impl From<bool> for ParameterValue {
+    fn from(value: bool) -> Self {
+        ParameterValue::Bool(value)
+    }
+}
+
+impl From<&str> for ParameterValue {
+    fn from(value: &str) -> Self {
+        ParameterValue::Text(value.to_string())
+    }
+}
+
+/// Errors produced when reading or writing tunable parameters by name.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ParameterError {
+    UnknownParameter(String),
+    TypeMismatch {
+        name: String,
+        expected: ParameterType,
+        found: ParameterType,
+    },
+    ValueNotAllowed {
+        name: String,
+        value: ParameterValue,
+    },
+    OutOfRange {
+        name: String,
+        value: i64,
+        min: i64,
+        max: i64,
+    },
+    InvalidJson {
+        name: String,
+        expected: ParameterType,
+        value: Value,
+    },
+}
+
+impl fmt::Display for ParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParameterError::UnknownParameter(name) => write!(f, "unknown parameter '{}'", name),
+            ParameterError::TypeMismatch {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "parameter '{}' expects a value of type {}, found {}",
+                name, expected, found
+            ),
+            ParameterError::ValueNotAllowed { name, value } => {
+                write!(f, "value {} is not allowed for parameter '{}'", value, name)
+            }
+            ParameterError::OutOfRange {
+                name,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "value {} of parameter '{}' is outside [{}, {}]",
+                value, name, min, max
+            ),
+            ParameterError::InvalidJson {
+                name,
+                expected,
+                value,
+            } => write!(
+                f,
+                "parameter '{}' expects a value of type {}, found {}",
+                name, expected, value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParameterError {}
+
+/// Declaration of a single tunable parameter: its name, default value and the values it may take.
+/// An empty list of possible values means any value of the parameter's type is accepted; in that
+/// case the parameter contributes only its default value to the search space. Int parameters may
+/// also be bounded, e.g. to fit the field they are stored in.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TunableParameter {
+    name: String,
+    default_value: ParameterValue,
+    possible_values: Vec<ParameterValue>,
+    // Inclusive bounds of an Int parameter
+    range: Option<(i64, i64)>,
+}
+
+impl TunableParameter {
+    pub fn new(name: &str, default_value: ParameterValue) -> Self {
+        Self {
+            name: name.to_string(),
+            default_value,
+            possible_values: vec![],
+            range: None,
+        }
+    }
+
+    pub fn new_with_values(
+        name: &str,
+        default_value: ParameterValue,
+        possible_values: Vec<ParameterValue>,
+    ) -> Self {
+        let expected = default_value.parameter_type();
+        assert!(
+            possible_values
+                .iter()
+                .all(|v| v.parameter_type() == expected),
+            "Possible values of parameter '{}' must all be of type {}",
+            name,
+            expected
+        );
+        Self {
+            name: name.to_string(),
+            default_value,
+            possible_values,
+            range: None,
+        }
+    }
+
+    /// Restricts an Int parameter to the values in [min, max], which random assignments sample
+    /// from. Panics if the parameter is not an Int, the range spans the whole of i64 or its
+    /// default value is out of the range.
+    pub fn with_range(mut self, min: i64, max: i64) -> Self {
+        let default = self
+            .default_value
+            .as_int()
+            .unwrap_or_else(|| panic!("Parameter '{}' with a range must be an Int", self.name));
+        assert!(
+            (min, max) != (i64::MIN, i64::MAX),
+            "Range of parameter '{}' must be bounded",
+            self.name
+        );
+        assert!(
+            (min..=max).contains(&default),
+            "Default value of parameter '{}' must be in its range",
+            self.name
+        );
+        self.range = Some((min, max));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn parameter_type(&self) -> ParameterType {
+        self.default_value.parameter_type()
+    }
+
+    pub fn default_value(&self) -> &ParameterValue {
+        &self.default_value
+    }
+
+    pub fn possible_values(&self) -> &Vec<ParameterValue> {
+        &self.possible_values
+    }
+
+    pub fn range(&self) -> Option<(i64, i64)> {
+        self.range
+    }
+
+    /// Values this parameter contributes to the search space
+    pub fn search_values(&self) -> Vec<ParameterValue> {
+        if self.possible_values.is_empty() {
+            vec![self.default_value.clone()]
+        } else {
+            self.possible_values.clone()
+        }
+    }
+
+    /// Checks that the value has the right type, is in the range (if bounded) and is one of the
+    /// possible values (if restricted)
+    pub fn validate(&self, value: &ParameterValue) -> Result<(), ParameterError> {
+        if value.parameter_type() != self.parameter_type() {
+            return Err(ParameterError::TypeMismatch {
+                name: self.name.clone(),
+                expected: self.parameter_type(),
+                found: value.parameter_type(),
+            });
+        }
+        if let (Some((min, max)), Some(v)) = (self.range, value.as_int()) {
+            if !(min..=max).contains(&v) {
+                return Err(ParameterError::OutOfRange {
+                    name: self.name.clone(),
+                    value: v,
+                    min,
+                    max,
+                });
+            }
+        }
+        if !self.possible_values.is_empty() && !self.possible_values.contains(value) {
+            return Err(ParameterError::ValueNotAllowed {
+                name: self.name.clone(),
+                value: value.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Ordered collection of tunable parameter declarations, which can be enumerated as a search space.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ParameterSpace {
+    parameters: Vec<TunableParameter>,
+}
+
+impl ParameterSpace {
+    pub fn new() -> Self {
+        ParameterSpace::default()
+    }
+
+    /// Adds a parameter declaration. Panics if a parameter with the same name already exists.
+    pub fn add(&mut self, parameter: TunableParameter) {
+        assert!(
+            self.get(parameter.name()).is_none(),
+            "Parameter '{}' declared twice",
+            parameter.name()
+        );
+        self.parameters.push(parameter);
+    }
+
+    /// Adds all the parameter declarations of another space
+    pub fn extend(&mut self, other: ParameterSpace) {
+        for parameter in other.parameters {
+            self.add(parameter);
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&TunableParameter> {
+        self.parameters.iter().find(|p| p.name() == name)
+    }
+
+    pub fn parameters(&self) -> &Vec<TunableParameter> {
+        &self.parameters
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.parameters.iter().map(|p| p.name.clone()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.parameters.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+    }
+
+    /// Reads a JSON value for the named parameter and checks it against its declaration
+    pub fn parse_json(&self, name: &str, value: &Value) -> Result<ParameterValue, ParameterError> {
+        let parameter = self
+            .get(name)
+            .ok_or_else(|| ParameterError::UnknownParameter(name.to_string()))?;
+        let parsed =
+            ParameterValue::from_json(value, parameter.parameter_type()).ok_or_else(|| {
+                ParameterError::InvalidJson {
+                    name: name.to_string(),
+                    expected: parameter.parameter_type(),
+                    value: value.clone(),
+                }
+            })?;
+        parameter.validate(&parsed)?;
+        Ok(parsed)
+    }
+
+    /// Checks the value against the declaration of the named parameter
+    pub fn validate(&self, name: &str, value: &ParameterValue) -> Result<(), ParameterError> {
+        self.get(name)
+            .ok_or_else(|| ParameterError::UnknownParameter(name.to_string()))?
+            .validate(value)
+    }
+
+    /// Assignment with the default value of every parameter
+    pub fn default_assignment(&self) -> ParameterAssignment {
+        self.parameters
+            .iter()
+            .map(|p| (p.name.clone(), p.default_value.clone()))
+            .collect()
+    }
+
+    /// Total number of assignments in the search space
+    pub fn size(&self) -> usize {
+        self.parameters
+            .iter()
+            .map(|p| p.search_values().len())
+            .product()
+    }
+
+    /// Decodes the index-th assignment of the search space (mixed radix, first parameter varies
+    /// fastest). Returns None if the index is out of range.
+    pub fn assignment(&self, index: usize) -> Option<ParameterAssignment> {
+        if index >= self.size() {
+            return None;
+        }
+        let mut remainder = index;
+        let mut assignment = ParameterAssignment::new();
+        for p in &self.parameters {
+            let values = p.search_values();
+            assignment.insert(p.name.clone(), values[remainder % values.len()].clone());
+            remainder /= values.len();
+        }
+        Some(assignment)
+    }
+
+    /// Samples a value uniformly at random for every parameter with declared possible values, or
+    /// within its range if it only has one. Parameters with neither are left out, so applying the
+    /// assignment keeps their current value instead of resetting them to the default.
+    pub fn random_assignment<R: Rng + ?Sized>(&self, rnd: &mut R) -> ParameterAssignment {
+        self.parameters
+            .iter()
+            .filter_map(|p| {
+                let value = if !p.possible_values.is_empty() {
+                    p.possible_values[rnd.gen_range(0..p.possible_values.len())].clone()
+                } else {
+                    let (min, max) = p.range?;
+                    ParameterValue::Int(rnd.gen_range(min..=max))
+                };
+                Some((p.name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Iterates over every assignment of the search space
+    pub fn assignments(&self) -> impl Iterator<Item = ParameterAssignment> + '_ {
+        (0..self.size()).filter_map(move |i| self.assignment(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn space() -> ParameterSpace {
+        let mut space = ParameterSpace::new();
+        space.add(TunableParameter::new_with_values(
+            "n_cards",
+            10.into(),
+            vec![5.into(), 10.into(), 15.into()],
+        ));
+        space.add(TunableParameter::new_with_values(
+            "open_hands",
+            false.into(),
+            vec![false.into(), true.into()],
+        ));
+        space.add(TunableParameter::new("name", "Default".into()));
+        space
+    }
+
+    #[test]
+    fn test_validate() {
+        let space = space();
+        assert!(space.validate("n_cards", &15.into()).is_ok());
+        assert_eq!(
+            space.validate("n_cards", &7.into()),
+            Err(ParameterError::ValueNotAllowed {
+                name: "n_cards".to_string(),
+                value: 7.into()
+            })
+        );
+        assert_eq!(
+            space.validate("open_hands", &1.into()),
+            Err(ParameterError::TypeMismatch {
+                name: "open_hands".to_string(),
+                expected: ParameterType::Bool,
+                found: ParameterType::Int
+            })
+        );
+        assert!(space.validate("name", &"Other".into()).is_ok());
+        assert_eq!(
+            space.validate("missing", &1.into()),
+            Err(ParameterError::UnknownParameter("missing".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_search_space_enumeration() {
+        let space = space();
+        assert_eq!(space.size(), 6);
+
+        let assignments: Vec<ParameterAssignment> = space.assignments().collect();
+        assert_eq!(assignments.len(), 6);
+        for (i, a) in assignments.iter().enumerate() {
+            assert_eq!(a["name"], "Default".into());
+            for (j, b) in assignments.iter().enumerate() {
+                assert_eq!(i == j, a == b);
+            }
+        }
+        assert_eq!(assignments[4]["n_cards"], 10.into());
+        assert_eq!(assignments[4]["open_hands"], true.into());
+        assert!(space.assignment(6).is_none());
+    }
+
+    #[test]
+    fn test_parse_json() {
+        let space = space();
+        assert_eq!(space.parse_json("n_cards", &json!(5)), Ok(5.into()));
+        assert_eq!(
+            space.parse_json("n_cards", &json!(5.5)),
+            Err(ParameterError::InvalidJson {
+                name: "n_cards".to_string(),
+                expected: ParameterType::Int,
+                value: json!(5.5)
+            })
+        );
+        assert!(space.parse_json("n_cards", &json!(6)).is_err());
+        assert_eq!(
+            space.parse_json("open_hands", &json!(true)),
+            Ok(true.into())
+        );
+        assert!(space.parse_json("name", &json!(null)).is_err());
+    }
+
+    #[test]
+    fn test_random_assignment() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut space = space();
+        space.add(TunableParameter::new("rounds", 1.into()).with_range(1, 4));
+        let mut rnd = StdRng::seed_from_u64(42);
+        for _ in 0..20 {
+            let assignment = space.random_assignment(&mut rnd);
+            for (name, value) in &assignment {
+                assert!(space.validate(name, value).is_ok());
+            }
+            assert!(!assignment.contains_key("name"));
+            assert!(assignment.contains_key("rounds"));
+        }
+
+        let a1 = space.random_assignment(&mut StdRng::seed_from_u64(7));
+        let a2 = space.random_assignment(&mut StdRng::seed_from_u64(7));
+        assert_eq!(a1, a2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_duplicated_parameter() {
+        let mut space = space();
+        space.add(TunableParameter::new("n_cards", 1.into()));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::main_code::core::{
+    components::component::Component, core_constants::VisibilityMode, game_state::GameState,
+    interfaces::component_container::IComponentContainer,
+};
+
+/// Returns true if the player can see the component at the given index of the container (in
+/// get_components order). With VisibleToOwner, teammates of the owner can also see it if the game
+/// shares team information.
+pub fn is_visible_at<C: IComponentContainer + ?Sized>(
+    gs: &dyn GameState,
+    container: &C,
+    index: usize,
+    player: usize,
+) -> bool {
+    let size = container.get_size();
+    if index >= size {
+        return false;
+    }
+    match container.get_visibility_mode() {
+        VisibilityMode::VisibleToAll => true,
+        VisibilityMode::HiddenToAll => false,
+        VisibilityMode::VisibleToOwner => gs.can_observe_owner(player, container.owner_id()),
+        VisibilityMode::FirstVisibleToAll => index == 0,
+        VisibilityMode::LastVisibleToAll => index + 1 == size,
+        VisibilityMode::MixedVisibility => container.is_visible_at(index, player),
+    }
+}
+
+/// Returns true if the player can see the component with the given ID, which must be held by the
+/// container (false otherwise).
+pub fn is_visible_to<C: IComponentContainer + ?Sized>(
+    gs: &dyn GameState,
+    container: &C,
+    component_id: usize,
+    player: usize,
+) -> bool {
+    container
+        .get_components()
+        .iter()
+        .position(|c| c.component_id() == component_id)
+        .is_some_and(|index| is_visible_at(gs, container, index, player))
+}
+
+/// Components of the container as seen by the player: hidden ones are None.
+pub fn visible_components<C: IComponentContainer + ?Sized>(
+    gs: &dyn GameState,
+    container: &C,
+    player: usize,
+) -> Vec<Option<Box<dyn Component>>> {
+    container
+        .get_components()
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| is_visible_at(gs, container, i, player).then_some(c))
+        .collect()
+}
+
+/// Indices of the components of the container that the player cannot see.
+pub fn hidden_indices<C: IComponentContainer + ?Sized>(
+    gs: &dyn GameState,
+    container: &C,
+    player: usize,
+) -> Vec<usize> {
+    (0..container.get_size())
+        .filter(|i| !is_visible_at(gs, container, *i, player))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::main_code::core::{
+        components::{
+            area::Area, card::Card, deck::Deck, partial_observable_deck::PartialObservableDeck,
+        },
+        game_parameters::AbstractParameters,
+        game_state::AbstractGameState,
+    };
+
+    fn deck(mode: VisibilityMode) -> Deck<Card> {
+        let mut deck = Deck::new_with_id("Hand", 0, 100, mode);
+        for id in 0..3 {
+            deck.add_to_bottom(Card::new_with_id("Card", id));
+        }
+        deck
+    }
+
+    fn visibility(
+        gs: &dyn GameState,
+        container: &dyn IComponentContainer,
+        player: usize,
+    ) -> Vec<bool> {
+        (0..container.get_size())
+            .map(|i| is_visible_at(gs, container, i, player))
+            .collect()
+    }
+
+    #[test]
+    fn test_all_modes() {
+        let mut gs = AbstractGameState::new(Box::new(AbstractParameters::new()), 3, 0);
+        gs.set_teams(vec![0, 1, 0]);
+
+        let all = deck(VisibilityMode::VisibleToAll);
+        assert_eq!(visibility(&gs, &all, 1), vec![true, true, true]);
+        let hidden = deck(VisibilityMode::HiddenToAll);
+        assert_eq!(visibility(&gs, &hidden, 0), vec![false, false, false]);
+        let first = deck(VisibilityMode::FirstVisibleToAll);
+        assert_eq!(visibility(&gs, &first, 1), vec![true, false, false]);
+        let last = deck(VisibilityMode::LastVisibleToAll);
+        assert_eq!(visibility(&gs, &last, 1), vec![false, false, true]);
+
+        let owned = deck(VisibilityMode::VisibleToOwner);
+        assert_eq!(visibility(&gs, &owned, 0), vec![true, true, true]);
+        assert_eq!(visibility(&gs, &owned, 2), vec![false, false, false]);
+        gs.set_share_team_information(true);
+        assert_eq!(visibility(&gs, &owned, 2), vec![true, true, true]);
+        assert_eq!(visibility(&gs, &owned, 1), vec![false, false, false]);
+
+        let mut mixed = PartialObservableDeck::new_with_id("Row", -1, 200, vec![false; 3]);
+        mixed.add(Card::new_with_id("Card", 10));
+        mixed.add(Card::new_with_id("Card", 11));
+        mixed.set_visibility_of_component(1, 2, true);
+        assert_eq!(visibility(&gs, &mixed, 2), vec![false, true]);
+        assert!(!is_visible_at(&gs, &mixed, 5, 2));
+    }
+
+    #[test]
+    fn test_area_visibility_is_settable() {
+        let gs = AbstractGameState::new(Box::new(AbstractParameters::new()), 2, 0);
+        let mut area = Area::new_with_id(1, 10);
+        area.put_component(Box::new(Card::new_with_id("Secret", 1)));
+        assert!(is_visible_to(&gs, &area, 1, 0));
+
+        area.set_visibility_mode(VisibilityMode::VisibleToOwner);
+        assert!(!is_visible_to(&gs, &area, 1, 0));
+        assert!(is_visible_to(&gs, &area, 1, 1));
+        assert!(!is_visible_to(&gs, &area, 99, 1));
+        assert!(visible_components(&gs, &area, 0)[0].is_none());
+        assert_eq!(hidden_indices(&gs, &area, 0), vec![0]);
+    }
+}
@@ -1,3 +1,4 @@
 pub mod core;
 pub mod games;
 pub mod players;
+pub mod utilities;
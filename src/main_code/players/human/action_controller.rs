@@ -15,6 +15,7 @@ pub struct ActionController {
 
 impl ActionController {
     // Constructor
+    #[allow(clippy::arc_with_non_send_sync, clippy::new_without_default)]
     pub fn new() -> Self {
         let (sender, receiver) = mpsc::channel();
         ActionController {
@@ -32,7 +33,7 @@ impl ActionController {
 
     // Add a new action to the queue from the GUI
     pub fn add_action_from_gui(&self, action: Box<dyn Action>) {
-        if let Err(_) = self.sender.send(action) {
+        if self.sender.send(action).is_err() {
             if self.debug {
                 println!("Error: Failed to send action to queue.");
             }
@@ -61,13 +62,14 @@ impl ActionController {
     }
 
     // Return the last action played
+    #[allow(clippy::borrowed_box)]
     pub fn get_last_action_played(&self) -> Option<&Box<dyn Action>> {
         self.last_action_played.as_ref()
     }
 
     // Check if there is an available action without blocking
     pub fn has_action(&self) -> bool {
-        !self.receiver.lock().unwrap().try_recv().is_err()
+        self.receiver.lock().unwrap().try_recv().is_ok()
     }
 
     // Reset the controller
@@ -97,7 +99,7 @@ mod tests {
             true
         }
 
-        fn get_string(&self, _gs: &Box<dyn GameState>) -> String {
+        fn get_string(&self, _gs: &dyn GameState) -> String {
             format!("TestAction {}", self.id)
         }
 
@@ -26,8 +26,9 @@ impl Player for HumanGUIPlayer {
     ) -> Box<dyn Action> {
         // Try to get the action from the ActionController
         self.ac.get_action().unwrap_or_else(|| {
-            // If no action is available, return a default action like DoNothing
-            Box::new(DoNothing::new())
+            // If no action is available, return a default action like DoNothing. The observation
+            // cannot allocate IDs, so it gets the same "no ID" value used for parent IDs.
+            Box::new(DoNothing::with_id(-1))
         })
     }
 }
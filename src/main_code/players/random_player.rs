@@ -1,15 +1,22 @@
 use crate::main_code::core::{actions::action::Action, game_state::GameState, player::Player};
-use rand::{prelude::ThreadRng, Rng};
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
 #[derive(Clone)]
 pub struct RandomPlayer {
-    rnd: ThreadRng,
+    rnd: StdRng,
 }
 
 impl RandomPlayer {
+    #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         RandomPlayer {
-            rnd: rand::thread_rng(),
+            rnd: StdRng::from_entropy(),
+        }
+    }
+
+    pub fn new_with_seed(seed: u64) -> Self {
+        RandomPlayer {
+            rnd: StdRng::seed_from_u64(seed),
         }
     }
 }
@@ -26,4 +33,8 @@ impl Player for RandomPlayer {
         let random_index = self.rnd.gen_range(0..actions.len());
         actions[random_index].clone()
     }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.rnd = StdRng::seed_from_u64(seed);
+    }
 }
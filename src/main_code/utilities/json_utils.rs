@@ -0,0 +1,59 @@
+use serde_json::Value;
+use std::{fmt, fs, io, path::Path};
+
+/// Errors produced when reading or writing JSON files.
+#[derive(Debug)]
+pub enum JsonError {
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    Parse {
+        path: String,
+        source: serde_json::Error,
+    },
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::Io { path, source } => write!(f, "could not access '{}': {}", path, source),
+            JsonError::Parse { path, source } => {
+                write!(f, "could not parse '{}': {}", path, source)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            JsonError::Io { source, .. } => Some(source),
+            JsonError::Parse { source, .. } => Some(source),
+        }
+    }
+}
+
+/// Reads and parses a JSON file.
+pub fn load_json_file(path: &Path) -> Result<Value, JsonError> {
+    let contents = fs::read_to_string(path).map_err(|source| JsonError::Io {
+        path: path.display().to_string(),
+        source,
+    })?;
+    serde_json::from_str(&contents).map_err(|source| JsonError::Parse {
+        path: path.display().to_string(),
+        source,
+    })
+}
+
+/// Writes a JSON value to a file, pretty printed.
+pub fn save_json_file(path: &Path, value: &Value) -> Result<(), JsonError> {
+    let contents = serde_json::to_string_pretty(value).map_err(|source| JsonError::Parse {
+        path: path.display().to_string(),
+        source,
+    })?;
+    fs::write(path, contents).map_err(|source| JsonError::Io {
+        path: path.display().to_string(),
+        source,
+    })
+}